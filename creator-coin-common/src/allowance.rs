@@ -0,0 +1,133 @@
+//! Allowance extension to NEP-141 shared by LZR and the creator coins. A holder approves a
+//! spender to pull up to an amount of their tokens until an optional expiry. The approver
+//! pays for the allowance's storage and gets it back when revoking.
+//!
+//! Contracts keep an [`Allowances`] in their state, expose `ft_approve`, `ft_revoke`,
+//! `ft_allowance` and `ft_transfer_from` on top of it and emit the events below under
+//! their own event standard.
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::LookupMap;
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::Serialize;
+use near_sdk::{env, require, AccountId, Balance, IntoStorageKey, Promise, Timestamp};
+
+use crate::refund_deposit;
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Allowance {
+    pub amount: Balance,
+    pub expires_at: Option<Timestamp>,
+}
+
+impl Allowance {
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| env::block_timestamp() >= expires_at)
+    }
+}
+
+/// Allowances keyed by owner and spender.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Allowances {
+    allowances: LookupMap<(AccountId, AccountId), Allowance>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ApproveEvent<'a> {
+    pub owner_id: &'a AccountId,
+    pub spender_id: &'a AccountId,
+    pub amount: U128,
+    pub expires_at: Option<U64>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TransferFromEvent<'a> {
+    pub owner_id: &'a AccountId,
+    pub spender_id: &'a AccountId,
+    pub receiver_id: &'a AccountId,
+    pub amount: U128,
+}
+
+impl Allowances {
+    pub fn new<S: IntoStorageKey>(prefix: S) -> Self {
+        Self {
+            allowances: LookupMap::new(prefix),
+        }
+    }
+
+    /// Lets `spender_id` transfer up to `amount` of `owner_id`'s tokens until `expires_at`,
+    /// replacing any previous allowance. The attached deposit pays for new storage and must
+    /// be at least one yoctoNEAR.
+    pub fn approve(
+        &mut self,
+        owner_id: &AccountId,
+        spender_id: &AccountId,
+        amount: Balance,
+        expires_at: Option<Timestamp>,
+    ) {
+        require!(
+            env::attached_deposit() >= 1,
+            "Requires attached deposit of at least 1 yoctoNEAR"
+        );
+        require!(owner_id != spender_id, "Cannot approve yourself");
+        if let Some(expires_at) = expires_at {
+            require!(
+                expires_at > env::block_timestamp(),
+                "Expiry must be in the future"
+            );
+        }
+
+        let initial_storage_usage = env::storage_usage();
+        self.allowances.insert(
+            &(owner_id.clone(), spender_id.clone()),
+            &Allowance { amount, expires_at },
+        );
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+    }
+
+    /// Removes the allowance of `spender_id` and refunds its storage to `owner_id`.
+    /// Requires exactly one attached yoctoNEAR, which is refunded as well.
+    pub fn revoke(&mut self, owner_id: &AccountId, spender_id: &AccountId) {
+        near_sdk::assert_one_yocto();
+        let initial_storage_usage = env::storage_usage();
+        require!(
+            self.allowances
+                .remove(&(owner_id.clone(), spender_id.clone()))
+                .is_some(),
+            "No allowance to revoke"
+        );
+        let released = env::storage_byte_cost()
+            * Balance::from(initial_storage_usage.saturating_sub(env::storage_usage()));
+        Promise::new(owner_id.clone()).transfer(released + 1);
+    }
+
+    /// Tokens `spender_id` can still transfer from `owner_id`, 0 once the allowance expired.
+    pub fn allowance(&self, owner_id: &AccountId, spender_id: &AccountId) -> Balance {
+        self.get(owner_id, spender_id)
+            .filter(|allowance| !allowance.is_expired())
+            .map_or(0, |allowance| allowance.amount)
+    }
+
+    /// The allowance `owner_id` gave `spender_id`, expired or not.
+    pub fn get(&self, owner_id: &AccountId, spender_id: &AccountId) -> Option<Allowance> {
+        self.allowances.get(&(owner_id.clone(), spender_id.clone()))
+    }
+
+    /// Takes `amount` out of the allowance `owner_id` gave `spender_id`.
+    /// The caller then transfers the tokens.
+    pub fn spend(&mut self, owner_id: &AccountId, spender_id: &AccountId, amount: Balance) {
+        let key = (owner_id.clone(), spender_id.clone());
+        let mut allowance = self
+            .allowances
+            .get(&key)
+            .filter(|allowance| !allowance.is_expired())
+            .unwrap_or_else(|| env::panic_str("No allowance"));
+        require!(amount > 0, "The amount should be a positive number");
+        require!(allowance.amount >= amount, "Amount exceeds allowance");
+        allowance.amount -= amount;
+        self.allowances.insert(&key, &allowance);
+    }
+}
@@ -0,0 +1,129 @@
+//! Signed intents shared by LZR and the creator coins, so a relayer can pay the gas for
+//! accounts that hold no NEAR. An account registers an ed25519 key once and then signs
+//! intents off-chain. The signed message is the intent's JSON text exactly as submitted.
+//! Nonces must be used in order, starting at 0, so every intent executes at most once.
+//!
+//! Contracts keep an [`IntentKeys`] in their state, define the actions an [`Intent`] can
+//! carry and execute them once [`IntentKeys::verify`] accepted the intent.
+
+use ed25519_dalek::Verifier;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::LookupMap;
+use near_sdk::json_types::U64;
+use near_sdk::serde::de::DeserializeOwned;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, require, AccountId, CurveType, IntoStorageKey, PublicKey};
+
+use crate::refund_deposit;
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct IntentKey {
+    pub public_key: PublicKey,
+    /// Nonce the next intent of the account must carry.
+    pub next_nonce: u64,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntentKeyView {
+    pub public_key: PublicKey,
+    pub next_nonce: U64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Intent<A> {
+    pub signer_id: AccountId,
+    /// Contract the intent is meant for, so it cannot be replayed on another one.
+    pub contract_id: AccountId,
+    pub nonce: U64,
+    /// Timestamp in nanoseconds after which the intent can no longer be executed.
+    pub expires_at: U64,
+    pub action: A,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntentExecutedEvent<'a> {
+    pub signer_id: &'a AccountId,
+    pub relayer_id: &'a AccountId,
+    pub nonce: U64,
+}
+
+/// Intent keys and nonces by account.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct IntentKeys {
+    keys: LookupMap<AccountId, IntentKey>,
+}
+
+impl IntentKeys {
+    pub fn new<S: IntoStorageKey>(prefix: S) -> Self {
+        Self {
+            keys: LookupMap::new(prefix),
+        }
+    }
+
+    /// Registers the ed25519 key `account_id` signs intents with, replacing any previous key.
+    /// Nonces carry over, so intents signed before a rotation stay spent.
+    /// The attached deposit pays for the key's storage and must be at least one yoctoNEAR.
+    pub fn register(&mut self, account_id: &AccountId, public_key: PublicKey) {
+        require!(
+            env::attached_deposit() >= 1,
+            "Requires attached deposit of at least 1 yoctoNEAR"
+        );
+        require!(
+            public_key.curve_type() == CurveType::ED25519,
+            "Only ed25519 keys are supported"
+        );
+        let next_nonce = self.keys.get(account_id).map_or(0, |key| key.next_nonce);
+
+        let initial_storage_usage = env::storage_usage();
+        self.keys.insert(
+            account_id,
+            &IntentKey {
+                public_key,
+                next_nonce,
+            },
+        );
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+    }
+
+    pub fn key_of(&self, account_id: &AccountId) -> Option<IntentKeyView> {
+        self.keys.get(account_id).map(|key| IntentKeyView {
+            public_key: key.public_key,
+            next_nonce: key.next_nonce.into(),
+        })
+    }
+
+    /// Parses `intent` and checks its target, expiry, nonce and signature, then spends the nonce.
+    pub fn verify<A: DeserializeOwned>(&mut self, intent: &str, signature: &[u8]) -> Intent<A> {
+        let parsed: Intent<A> = near_sdk::serde_json::from_str(intent)
+            .unwrap_or_else(|_| env::panic_str("Invalid intent"));
+        require!(
+            parsed.contract_id == env::current_account_id(),
+            "Intent is for another contract"
+        );
+        require!(
+            env::block_timestamp() < parsed.expires_at.0,
+            "Intent has expired"
+        );
+        let mut key = self
+            .keys
+            .get(&parsed.signer_id)
+            .unwrap_or_else(|| env::panic_str("Signer has no intent key"));
+        require!(parsed.nonce.0 == key.next_nonce, "Invalid nonce");
+
+        let public_key = ed25519_dalek::PublicKey::from_bytes(&key.public_key.as_bytes()[1..])
+            .unwrap_or_else(|_| env::panic_str("Invalid intent key"));
+        let signature = ed25519_dalek::Signature::try_from(signature)
+            .unwrap_or_else(|_| env::panic_str("Invalid signature"));
+        require!(
+            public_key.verify(intent.as_bytes(), &signature).is_ok(),
+            "Invalid signature"
+        );
+
+        key.next_nonce += 1;
+        self.keys.insert(&parsed.signer_id, &key);
+        parsed
+    }
+}
@@ -0,0 +1,190 @@
+//! Types shared by the creator coin factory and the creator coin contract,
+//! so the factory's init call cannot drift from what the coin expects.
+
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::serde_json::json;
+use near_sdk::{env, require, AccountId, Balance, Promise};
+
+pub mod allowance;
+pub mod intents;
+
+/// Init method the factory calls on a freshly deployed creator coin.
+pub const CREATOR_COIN_INIT_METHOD: &str = "new_from_args";
+
+/// JSON key of the [`CreatorCoinArgs`] passed to [`CREATOR_COIN_INIT_METHOD`], which is the
+/// name of the init method's parameter.
+pub const CREATOR_COIN_INIT_ARG: &str = "args";
+
+/// Method the factory calls with a new wasm as raw input to upgrade a coin the owner approved.
+pub const CREATOR_COIN_UPDATE_METHOD: &str = "update_code";
+
+/// Method the factory calls to configure a coin's referral rewards.
+pub const CREATOR_COIN_REFERRAL_METHOD: &str = "set_referral_config";
+
+/// Method the factory calls to start winding a coin down.
+pub const CREATOR_COIN_WIND_DOWN_METHOD: &str = "wind_down";
+
+/// Method the factory calls to change a coin's symbol once the new one is reserved.
+pub const CREATOR_COIN_SYMBOL_METHOD: &str = "set_symbol";
+
+/// Method a coin calls on itself right after new code is deployed.
+pub const CREATOR_COIN_MIGRATE_METHOD: &str = "migrate";
+
+/// Creator admin methods an optional function-call key on a coin account may call.
+pub const CREATOR_ADMIN_METHODS: &str = "configure_staking,fund_staking_rewards";
+
+pub const TOKEN_DECIMAL: u8 = 24;
+
+/// Shape of a coin's bonding curve.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CurveParams {
+    /// Share of the market cap held in reserve, between 0 and 1.
+    pub reserve_ratio: f64,
+    /// Price slope used to mint the first coins while the reserve is empty.
+    pub slope: f64,
+}
+
+impl Default for CurveParams {
+    fn default() -> Self {
+        Self {
+            reserve_ratio: 0.3333333333333333,
+            slope: 0.003,
+        }
+    }
+}
+
+impl CurveParams {
+    pub fn assert_valid(&self) {
+        require!(
+            self.reserve_ratio > 0. && self.reserve_ratio <= 1.,
+            "Reserve ratio must be between 0 and 1"
+        );
+        require!(self.slope > 0., "Slope must be positive");
+    }
+}
+
+/// Everything a creator coin needs at init.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CreatorCoinArgs {
+    /// The artist who owns the coin.
+    pub creator_id: AccountId,
+    /// Fungible token the coin is bought with and keeps in reserve.
+    pub reserve_token_id: AccountId,
+    /// Name, symbol and icon shown in wallets.
+    pub metadata: FungibleTokenMetadata,
+    pub curve: CurveParams,
+}
+
+impl CreatorCoinArgs {
+    /// JSON arguments for calling [`CREATOR_COIN_INIT_METHOD`] with these args.
+    pub fn to_init_payload(&self) -> Vec<u8> {
+        let mut payload = near_sdk::serde_json::Map::new();
+        payload.insert(CREATOR_COIN_INIT_ARG.to_string(), json!(self));
+        near_sdk::serde_json::to_vec(&payload).unwrap()
+    }
+}
+
+/// `msg` of an LZR `ft_transfer_call` to a creator coin that buys coins with the LZR sent.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BuyMessage {
+    /// Account the coins are minted to, the LZR sender by default.
+    pub receiver_id: Option<AccountId>,
+    /// Least amount of coins to mint. Buys below it fail and return the LZR.
+    pub min_out: Option<U128>,
+    /// Account that brought the buyer in and earns a share of the protocol fee.
+    pub referrer_id: Option<AccountId>,
+}
+
+impl BuyMessage {
+    pub fn to_msg(&self) -> String {
+        near_sdk::serde_json::to_string(self).unwrap()
+    }
+}
+
+/// How much of the protocol fee on a buy goes to the referrer.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReferralConfig {
+    /// Share of the protocol fee in basis points.
+    pub share_bps: u16,
+    /// Most LZR a referrer can earn per day.
+    pub daily_cap: U128,
+}
+
+/// Referral rewards are off until the factory turns them on.
+impl Default for ReferralConfig {
+    fn default() -> Self {
+        Self {
+            share_bps: 0,
+            daily_cap: 0.into(),
+        }
+    }
+}
+
+/// Charges the predecessor for `storage_used` bytes out of the attached deposit
+/// and refunds whatever is left over.
+pub fn refund_deposit(storage_used: u64) {
+    let required_cost = env::storage_byte_cost() * Balance::from(storage_used);
+    let attached_deposit = env::attached_deposit();
+    require!(
+        required_cost <= attached_deposit,
+        format!("Must attach {} yoctoNEAR to cover storage", required_cost)
+    );
+
+    let refund = attached_deposit - required_cost;
+    if refund > 1 {
+        Promise::new(env::predecessor_account_id()).transfer(refund);
+    }
+}
+
+/// Implemented by the creator coin contract. The trait pins the signature of
+/// [`CREATOR_COIN_INIT_METHOD`], so changing it without changing [`CreatorCoinArgs`]
+/// fails to compile.
+pub trait CreatorCoinInit {
+    fn new_from_args(args: CreatorCoinArgs) -> Self;
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_contract_standards::fungible_token::metadata::FT_METADATA_SPEC;
+    use near_sdk::serde_json::Value;
+    use near_sdk::test_utils::accounts;
+
+    use super::*;
+
+    #[test]
+    fn test_init_payload() {
+        let args = CreatorCoinArgs {
+            creator_id: accounts(1),
+            reserve_token_id: accounts(2),
+            metadata: FungibleTokenMetadata {
+                spec: FT_METADATA_SPEC.to_string(),
+                name: "Burna Coin".to_string(),
+                symbol: "BURNA".to_string(),
+                icon: Some("data:image/png;base64,".to_string()),
+                reference: None,
+                reference_hash: None,
+                decimals: TOKEN_DECIMAL,
+            },
+            curve: CurveParams::default(),
+        };
+
+        let payload: Value = near_sdk::serde_json::from_slice(&args.to_init_payload()).unwrap();
+        let decoded: CreatorCoinArgs =
+            near_sdk::serde_json::from_value(payload[CREATOR_COIN_INIT_ARG].clone()).unwrap();
+        if decoded.creator_id != accounts(1) || decoded.metadata.symbol != "BURNA" {
+            panic!("INIT PAYLOAD DOES NOT ROUND TRIP");
+        }
+        if decoded.curve != CurveParams::default() {
+            panic!("CURVE PARAMS DO NOT ROUND TRIP");
+        }
+    }
+}
@@ -3,4 +3,9 @@
 ./build.sh
 
 echo ">> Deploying contract..."
-near deploy --wasmFile ./res/creator_coin_factory.wasm --accountId $1
\ No newline at end of file
+near deploy --wasmFile ./res/creator_coin_factory.wasm --accountId $1
+
+echo ">> Uploading creator coin code..."
+near call $1 upload_code --base64 "$(base64 -w0 ../profile-token-contract/res/loozr_creator_token.wasm)" --accountId $1 --deposit 5 --gas 300000000000000
+
+echo ">> Call set_default_version with the returned version to start creating coins"
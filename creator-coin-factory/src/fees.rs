@@ -0,0 +1,136 @@
+use crate::*;
+
+/// LZR charged for every coin created by a non-admin, sent to the protocol treasury.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CreationFee {
+    pub amount: U128,
+    pub treasury_id: AccountId,
+}
+
+#[near_bindgen]
+impl CreatorCoinFactory {
+    /// Sets the LZR creation fee, or removes it with `None`.
+    pub fn set_creation_fee(&mut self, creation_fee: Option<CreationFee>) {
+        self.assert_admin();
+        self.creation_fee = creation_fee;
+    }
+
+    pub fn get_creation_fee(&self) -> Option<CreationFee> {
+        self.creation_fee.clone()
+    }
+
+    /// Sends `amount` of the factory's LZR to `receiver_id`, such as the reserves swept
+    /// from coins that wound down without a treasury.
+    #[payable]
+    pub fn withdraw_lzr(&mut self, receiver_id: AccountId, amount: U128) -> Promise {
+        near_sdk::assert_one_yocto();
+        self.assert_admin();
+        ext_ft_transfer::ext(get_lzr_token_contract())
+            .with_attached_deposit(1)
+            .ft_transfer(receiver_id, amount)
+    }
+
+    /// Prepays NEAR for the storage of coins `account_id`, the caller by default,
+    /// creates through an LZR transfer. Transfer calls cannot carry NEAR themselves.
+    #[payable]
+    pub fn deposit_create_funds(&mut self, account_id: Option<AccountId>) -> U128 {
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let initial_storage_usage = env::storage_usage();
+        let funds = self.create_funds.get(&account_id).unwrap_or(0);
+        self.create_funds.insert(&account_id, &funds);
+        let entry_cost = env::storage_byte_cost()
+            * Balance::from(env::storage_usage().saturating_sub(initial_storage_usage));
+        require!(
+            env::attached_deposit() > entry_cost,
+            format!("Must attach more than {} yoctoNEAR", entry_cost)
+        );
+
+        let funds = funds + env::attached_deposit() - entry_cost;
+        self.create_funds.insert(&account_id, &funds);
+        funds.into()
+    }
+
+    /// Withdraws `amount` of the caller's prepaid NEAR, everything by default.
+    pub fn withdraw_create_funds(&mut self, amount: Option<U128>) -> U128 {
+        let account_id = env::predecessor_account_id();
+        let funds = self.create_funds.get(&account_id).unwrap_or(0);
+        let amount = amount.map(Balance::from).unwrap_or(funds);
+        require!(amount > 0, "Nothing to withdraw");
+        require!(amount <= funds, "Amount exceeds prepaid funds");
+
+        self.create_funds.insert(&account_id, &(funds - amount));
+        Promise::new(account_id).transfer(amount);
+        amount.into()
+    }
+
+    pub fn create_funds_of(&self, account_id: AccountId) -> U128 {
+        self.create_funds.get(&account_id).unwrap_or(0).into()
+    }
+
+    /// Callback after a coin paid for with LZR was created. On success the fee goes to the
+    /// treasury and the rest of the transfer is returned. On failure the whole transfer is
+    /// returned and the prepaid NEAR that came back is credited to `sender_id` again.
+    pub fn on_coin_create_with_fee(
+        &mut self,
+        sender_id: AccountId,
+        coin: PendingCoin,
+        amount: U128,
+        creation_fee: Option<CreationFee>,
+    ) -> U128 {
+        assert_self();
+
+        let forwarded_deposit = coin.forwarded_deposit.0;
+        if !self.internal_resolve_create(coin) {
+            let refund = returned_deposit(forwarded_deposit);
+            let funds = self.create_funds.get(&sender_id).unwrap_or(0);
+            self.create_funds.insert(&sender_id, &(funds + refund));
+            return amount;
+        }
+        match creation_fee {
+            Some(CreationFee {
+                amount: fee,
+                treasury_id,
+            }) if fee.0 > 0 => {
+                ext_ft_transfer::ext(get_lzr_token_contract())
+                    .with_attached_deposit(1)
+                    .ft_transfer(treasury_id, fee);
+                (amount.0 - fee.0).into()
+            }
+            _ => amount,
+        }
+    }
+}
+
+impl CreatorCoinFactory {
+    /// Creates the coin described by `create` for `amount` LZR sent by `sender_id`,
+    /// paying its storage out of the sender's prepaid NEAR.
+    pub(crate) fn internal_create_with_fee(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        create: CreateArgs,
+    ) -> PromiseOrValue<U128> {
+        let creation_fee = self.creation_fee.clone();
+        let fee = creation_fee.as_ref().map(|fee| fee.amount.0).unwrap_or(0);
+        require!(
+            amount.0 >= fee,
+            format!("Creating a coin costs {} LZR", fee)
+        );
+        let funds = self.create_funds.get(&sender_id).unwrap_or(0);
+        let (promise, coin) = self.internal_create(&sender_id, create, funds);
+        self.create_funds
+            .insert(&sender_id, &(funds - coin.forwarded_deposit.0));
+
+        promise
+            .then(
+                Self::ext(env::current_account_id()).on_coin_create_with_fee(
+                    sender_id,
+                    coin,
+                    amount,
+                    creation_fee,
+                ),
+            )
+            .into()
+    }
+}
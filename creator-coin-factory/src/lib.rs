@@ -1,63 +1,1147 @@
+// `create` takes one argument per coin setting and near_bindgen copies them into generated code.
+#![allow(clippy::too_many_arguments)]
+
+use creator_coin_common::{
+    CreatorCoinArgs, CurveParams, ReferralConfig, CREATOR_ADMIN_METHODS, CREATOR_COIN_INIT_METHOD,
+    CREATOR_COIN_REFERRAL_METHOD, CREATOR_COIN_SYMBOL_METHOD, CREATOR_COIN_WIND_DOWN_METHOD,
+    TOKEN_DECIMAL,
+};
+use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::json_types::U128;
-use near_sdk::serde::Serialize;
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
-    assert_self, env, is_promise_success, near_bindgen, AccountId, Balance, Gas, Promise,
+    assert_self, env, ext_contract, is_promise_success, log, near_bindgen, require, AccountId,
+    Balance, BorshStorageKey, CryptoHash, Gas, Promise, PromiseOrValue, PromiseResult, PublicKey,
 };
 
-const CODE: &[u8] = include_bytes!("../../profile-token-contract/res/loozr_creator_token.wasm");
+pub use crate::fees::*;
+pub use crate::versions::*;
+
+mod fees;
+mod router;
+mod versions;
 
 const NO_DEPOSIT: Balance = 0;
 const CREATE_CALL_GAS: u64 = 25_000_000_000_000;
+const REFERRAL_CALL_GAS: u64 = 10_000_000_000_000;
+const WIND_DOWN_CALL_GAS: u64 = 10_000_000_000_000;
+const SYMBOL_CALL_GAS: u64 = 10_000_000_000_000;
+/// Gas allowance of the optional creator admin key, paid for by the creation deposit.
+const ADMIN_KEY_ALLOWANCE: Balance = 250_000_000_000_000_000_000_000;
+/// Storage a new coin uses besides its code and metadata: the account record,
+/// its access keys and the contract's fixed state.
+const COIN_BASE_STORAGE: u64 = 2_000;
+const MAX_SYMBOL_LENGTH: usize = 10;
 
 #[near_bindgen]
-#[derive(BorshDeserialize, BorshSerialize, Default)]
-pub struct CreatorCoinFactory {}
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct CreatorCoinFactory {
+    symbols: LookupSet<String>,
+    coins: UnorderedMap<AccountId, CoinInfo>,
+    coins_by_owner: LookupMap<AccountId, Vec<AccountId>>,
+    /// Owners with a coin creation in flight.
+    pending_owners: LookupSet<AccountId>,
+    /// Coins with a symbol change in flight.
+    pending_symbol_updates: LookupSet<AccountId>,
+    /// Accounts besides the factory itself that manage reserved names and coin statuses.
+    admins: UnorderedSet<AccountId>,
+    /// Coin names only admins can create, e.g. well known artists.
+    reserved_names: UnorderedSet<String>,
+    /// Creator coin wasm blobs keyed by their sha256.
+    codes: UnorderedMap<CryptoHash, Vec<u8>>,
+    /// Code new coins are created with.
+    default_version: Option<CryptoHash>,
+    upgrade_progress: Option<UpgradeProgress>,
+    /// LZR charged for creating a coin, if any.
+    creation_fee: Option<CreationFee>,
+    /// NEAR prepaid for the storage of coins created through an LZR transfer.
+    create_funds: LookupMap<AccountId, Balance>,
+}
+
+impl Default for CreatorCoinFactory {
+    fn default() -> Self {
+        Self {
+            symbols: LookupSet::new(StorageKey::Symbols),
+            coins: UnorderedMap::new(StorageKey::Coins),
+            coins_by_owner: LookupMap::new(StorageKey::CoinsByOwner),
+            pending_owners: LookupSet::new(StorageKey::PendingOwners),
+            pending_symbol_updates: LookupSet::new(StorageKey::PendingSymbolUpdates),
+            admins: UnorderedSet::new(StorageKey::Admins),
+            reserved_names: UnorderedSet::new(StorageKey::ReservedNames),
+            codes: UnorderedMap::new(StorageKey::Codes),
+            default_version: None,
+            upgrade_progress: None,
+            creation_fee: None,
+            create_funds: LookupMap::new(StorageKey::CreateFunds),
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+enum StorageKey {
+    Symbols,
+    Coins,
+    CoinsByOwner,
+    PendingOwners,
+    PendingSymbolUpdates,
+    Admins,
+    ReservedNames,
+    Codes,
+    CreateFunds,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum CoinStatus {
+    Active,
+    Paused,
+    Delisted,
+}
 
-#[derive(Serialize)]
+/// Registry entry for a creator coin deployed by this factory.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
-pub struct CreatorCoinArgs {
-    owner_id: AccountId,
+pub struct CoinInfo {
+    pub owner_id: AccountId,
+    pub symbol: String,
+    /// Nanosecond timestamp the coin was created at.
+    pub created_at: U64,
+    /// sha256 of the wasm the coin is running.
+    pub code_version: Base58CryptoHash,
+    pub status: CoinStatus,
+}
+
+/// Everything `create` needs, also accepted as the `create` message of an LZR transfer.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CreateArgs {
+    pub owner_id: AccountId,
+    pub name: String,
+    pub token_name: String,
+    pub symbol: String,
+    pub icon: Option<String>,
+    pub curve: Option<CurveParams>,
+    pub admin_public_key: Option<PublicKey>,
+}
+
+/// A coin whose creation is in flight, passed to the creation callbacks.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingCoin {
+    pub coin_account_id: AccountId,
+    pub owner_id: AccountId,
+    pub symbol: String,
+    pub code_version: Base58CryptoHash,
+    /// NEAR sent along to the coin account.
+    pub forwarded_deposit: U128,
+}
+
+#[ext_contract(ext_ft_transfer)]
+pub trait LoozrFt {
+    fn ft_transfer(receiver_id: AccountId, amount: U128);
+    fn ft_transfer_call(
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128>;
+}
+
+/// Reserve token every creator coin is bought with.
+fn get_lzr_token_contract() -> AccountId {
+    "lzr.testnet".parse().unwrap()
 }
 
 #[near_bindgen]
 impl CreatorCoinFactory {
+    /// Replaces the empty state of a factory deployed before the coin registry existed.
+    /// Coins it created before are not in the registry.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        #[derive(BorshDeserialize)]
+        struct OldCreatorCoinFactory {}
+
+        let _old: OldCreatorCoinFactory =
+            env::state_read().unwrap_or_else(|| env::panic_str("No state"));
+        Self::default()
+    }
+
+    /// Deploys the default code version as a creator coin to `{name}.{factory}` showing
+    /// `token_name` and `symbol` in wallets.
+    /// Symbols are unique across every coin created by this factory.
+    /// `curve` defaults to the standard Loozr bonding curve.
+    ///
+    /// Each owner can have one active coin, creates it themselves and reserved names are
+    /// off limits, unless the caller is an admin.
+    ///
+    /// The attached deposit must cover the coin's storage, see `get_create_deposit`.
+    /// Anything above that is refunded right away.
+    ///
+    /// The coin account gets no full access key. The owner can pass `admin_public_key`
+    /// to add a function-call key limited to [`CREATOR_ADMIN_METHODS`] on the coin, whose
+    /// gas allowance is added to the required deposit.
+    ///
+    /// While a creation fee is set, only admins can call this directly. Everyone else
+    /// creates coins by paying the fee with `ft_transfer_call` on LZR.
     #[payable]
-    pub fn create(&mut self, owner_id: AccountId, name: String) -> Promise {
-        let creator_coin_account_id = format!("{}.{}", name, env::current_account_id());
+    pub fn create(
+        &mut self,
+        owner_id: AccountId,
+        name: String,
+        token_name: String,
+        symbol: String,
+        icon: Option<String>,
+        curve: Option<CurveParams>,
+        admin_public_key: Option<PublicKey>,
+    ) -> Promise {
+        let predecessor_id = env::predecessor_account_id();
+        require!(
+            self.creation_fee.is_none() || self.is_admin(predecessor_id.clone()),
+            "Creating a coin costs an LZR fee, use ft_transfer_call on LZR"
+        );
+        let (promise, coin) = self.internal_create(
+            &predecessor_id,
+            CreateArgs {
+                owner_id,
+                name,
+                token_name,
+                symbol,
+                icon,
+                curve,
+                admin_public_key,
+            },
+            env::attached_deposit(),
+        );
 
-        Promise::new(creator_coin_account_id.parse().unwrap())
-            .create_account()
-            .transfer(env::attached_deposit())
-            .add_full_access_key(env::signer_account_pk())
-            .deploy_contract(CODE.to_vec())
+        let excess = env::attached_deposit() - coin.forwarded_deposit.0;
+        if excess > 0 {
+            Promise::new(predecessor_id.clone()).transfer(excess);
+        }
+        promise.then(Self::ext(env::current_account_id()).on_coin_create(predecessor_id, coin))
+    }
+
+    /// Callback after a creator coin was created.
+    /// Returns true and records the coin in the registry if the coin creation succeeded.
+    /// Otherwise releases the symbol, refunds the deposit to `payer_id` and returns `false`.
+    pub fn on_coin_create(&mut self, payer_id: AccountId, coin: PendingCoin) -> bool {
+        assert_self();
+
+        let forwarded_deposit = coin.forwarded_deposit.0;
+        if self.internal_resolve_create(coin) {
+            return true;
+        }
+        let refund = returned_deposit(forwarded_deposit);
+        if refund > 0 {
+            Promise::new(payer_id).transfer(refund);
+        }
+        return false;
+    }
+
+    /// Minimum deposit `create` needs for a coin with this metadata running the default code.
+    pub fn get_create_deposit(
+        &self,
+        token_name: String,
+        symbol: String,
+        icon: Option<String>,
+    ) -> U128 {
+        let (_, code) = self.internal_default_code();
+        coin_storage_deposit(
+            code.len(),
+            &FungibleTokenMetadata {
+                spec: FT_METADATA_SPEC.to_string(),
+                name: token_name,
+                symbol,
+                icon,
+                reference: None,
+                reference_hash: None,
+                decimals: TOKEN_DECIMAL,
+            },
+        )
+        .into()
+    }
+
+    pub fn is_symbol_available(&self, symbol: String) -> bool {
+        !self.symbols.contains(&symbol)
+    }
+
+    /// Changes the symbol of the caller's coin `coin_account_id` to `symbol`, which must be
+    /// available. The attached deposit is forwarded to the coin to pay for its metadata.
+    #[payable]
+    pub fn update_coin_symbol(&mut self, coin_account_id: AccountId, symbol: String) -> Promise {
+        let coin = self
+            .coins
+            .get(&coin_account_id)
+            .unwrap_or_else(|| env::panic_str("Coin not found"));
+        let owner_id = env::predecessor_account_id();
+        require!(
+            owner_id == coin.owner_id,
+            "Only the coin's owner can change its symbol"
+        );
+        assert_valid_symbol(&symbol);
+        require!(
+            self.pending_symbol_updates.insert(&coin_account_id),
+            "A symbol change is already in flight for this coin"
+        );
+        require!(
+            self.symbols.insert(&symbol),
+            format!("Symbol {} is already taken", symbol)
+        );
+
+        Promise::new(coin_account_id.clone())
             .function_call(
-                "new_default_meta".into(),
-                near_sdk::serde_json::to_vec(&CreatorCoinArgs { owner_id }).unwrap(),
-                NO_DEPOSIT,
-                Gas(CREATE_CALL_GAS),
+                CREATOR_COIN_SYMBOL_METHOD.into(),
+                near_sdk::serde_json::to_vec(&near_sdk::serde_json::json!({ "symbol": symbol }))
+                    .unwrap(),
+                env::attached_deposit(),
+                Gas(SYMBOL_CALL_GAS),
             )
-            .then(Self::ext(env::current_account_id()).on_coin_create(
+            .then(Self::ext(env::current_account_id()).on_coin_symbol_updated(
+                coin_account_id,
+                symbol,
+                owner_id,
                 env::attached_deposit().into(),
-                env::predecessor_account_id(),
             ))
     }
 
-    /// Callback after a creator coin was created.
-    /// Returns true if the coin creation succeeded.
-    /// Otherwise refunds the attached deposit and returns `false`.
-    pub fn on_coin_create(
+    /// Callback after a coin was asked to change its symbol. Releases the old symbol if it
+    /// did, otherwise releases the new one and refunds `deposit` to `owner_id`.
+    pub fn on_coin_symbol_updated(
         &mut self,
-        attached_deposit: U128,
-        predecessor_account_id: AccountId,
+        coin_account_id: AccountId,
+        symbol: String,
+        owner_id: AccountId,
+        deposit: U128,
     ) -> bool {
         assert_self();
 
-        let coin_created = is_promise_success();
-        if coin_created {
-            return true;
+        self.pending_symbol_updates.remove(&coin_account_id);
+        if !is_promise_success() {
+            self.symbols.remove(&symbol);
+            if deposit.0 > 0 {
+                Promise::new(owner_id).transfer(deposit.0);
+            }
+            return false;
+        }
+        let mut coin = self.coins.get(&coin_account_id).unwrap();
+        self.symbols.remove(&coin.symbol);
+        log!(
+            "Coin @{} changed its symbol from {} to {}",
+            coin_account_id,
+            coin.symbol,
+            symbol
+        );
+        coin.symbol = symbol;
+        self.coins.insert(&coin_account_id, &coin);
+        true
+    }
+
+    pub fn add_admin(&mut self, account_id: AccountId) {
+        require!(
+            env::predecessor_account_id() == env::current_account_id(),
+            "Only the factory can manage admins"
+        );
+        self.admins.insert(&account_id);
+    }
+
+    pub fn remove_admin(&mut self, account_id: AccountId) {
+        require!(
+            env::predecessor_account_id() == env::current_account_id(),
+            "Only the factory can manage admins"
+        );
+        self.admins.remove(&account_id);
+    }
+
+    pub fn is_admin(&self, account_id: AccountId) -> bool {
+        account_id == env::current_account_id() || self.admins.contains(&account_id)
+    }
+
+    pub fn get_admins(&self) -> Vec<AccountId> {
+        self.admins.to_vec()
+    }
+
+    pub fn add_reserved_names(&mut self, names: Vec<String>) {
+        self.assert_admin();
+        for name in names {
+            self.reserved_names.insert(&name);
+        }
+    }
+
+    pub fn remove_reserved_names(&mut self, names: Vec<String>) {
+        self.assert_admin();
+        for name in names {
+            self.reserved_names.remove(&name);
+        }
+    }
+
+    pub fn get_reserved_names(&self) -> Vec<String> {
+        self.reserved_names.to_vec()
+    }
+
+    /// Sets how much of the protocol fee on buys of `coin_account_id` goes to referrers.
+    pub fn set_coin_referral_config(
+        &mut self,
+        coin_account_id: AccountId,
+        config: ReferralConfig,
+    ) -> Promise {
+        self.assert_admin();
+        require!(self.coins.get(&coin_account_id).is_some(), "Coin not found");
+        Promise::new(coin_account_id).function_call(
+            CREATOR_COIN_REFERRAL_METHOD.into(),
+            near_sdk::serde_json::to_vec(&near_sdk::serde_json::json!({ "config": config }))
+                .unwrap(),
+            NO_DEPOSIT,
+            Gas(REFERRAL_CALL_GAS),
+        )
+    }
+
+    /// Pausing or delisting a coin frees its owner to create another one.
+    pub fn set_coin_status(&mut self, account_id: AccountId, status: CoinStatus) {
+        self.assert_admin();
+        let mut coin = self
+            .coins
+            .get(&account_id)
+            .unwrap_or_else(|| env::panic_str("Coin not found"));
+        coin.status = status;
+        self.coins.insert(&account_id, &coin);
+    }
+
+    /// Delists `coin_account_id` and starts its wind-down, giving holders `redemption_period`
+    /// nanoseconds to redeem. The remaining reserve goes to the creation fee treasury if one
+    /// is set, otherwise to the factory, see `withdraw_lzr`.
+    pub fn wind_down_coin(
+        &mut self,
+        coin_account_id: AccountId,
+        redemption_period: U64,
+    ) -> Promise {
+        self.assert_admin();
+        let mut coin = self
+            .coins
+            .get(&coin_account_id)
+            .unwrap_or_else(|| env::panic_str("Coin not found"));
+        coin.status = CoinStatus::Delisted;
+        self.coins.insert(&coin_account_id, &coin);
+
+        let treasury_id = self
+            .creation_fee
+            .as_ref()
+            .map(|fee| fee.treasury_id.clone());
+        Promise::new(coin_account_id).function_call(
+            CREATOR_COIN_WIND_DOWN_METHOD.into(),
+            near_sdk::serde_json::to_vec(&near_sdk::serde_json::json!({
+                "redemption_period": redemption_period,
+                "treasury_id": treasury_id,
+            }))
+            .unwrap(),
+            NO_DEPOSIT,
+            Gas(WIND_DOWN_CALL_GAS),
+        )
+    }
+
+    pub fn get_coin(&self, account_id: AccountId) -> Option<CoinInfo> {
+        self.coins.get(&account_id)
+    }
+
+    pub fn get_coins(
+        &self,
+        from_index: Option<U64>,
+        limit: Option<u64>,
+    ) -> Vec<(AccountId, CoinInfo)> {
+        let coin_account_ids = self.coins.keys_as_vector();
+        let coin_infos = self.coins.values_as_vector();
+        let from_index = from_index.map(u64::from).unwrap_or(0);
+        let end = coin_account_ids
+            .len()
+            .min(from_index.saturating_add(limit.unwrap_or(u64::MAX)));
+        (from_index..end)
+            .map(|index| {
+                (
+                    coin_account_ids.get(index).unwrap(),
+                    coin_infos.get(index).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn get_coins_by_owner(&self, owner_id: AccountId) -> Vec<(AccountId, CoinInfo)> {
+        self.coins_by_owner
+            .get(&owner_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|account_id| self.coins.get(&account_id).map(|coin| (account_id, coin)))
+            .collect()
+    }
+}
+
+impl CreatorCoinFactory {
+    /// Checks `args` and builds the promise that creates, funds and initializes the coin
+    /// out of `available_deposit`. `caller_id` is who asked for the coin, for the admin checks.
+    pub(crate) fn internal_create(
+        &mut self,
+        caller_id: &AccountId,
+        args: CreateArgs,
+        available_deposit: Balance,
+    ) -> (Promise, PendingCoin) {
+        let CreateArgs {
+            owner_id,
+            name,
+            token_name,
+            symbol,
+            icon,
+            curve,
+            admin_public_key,
+        } = args;
+        let creator_coin_account_id = assert_valid_coin_name(&name);
+        assert_valid_symbol(&symbol);
+        let curve = curve.unwrap_or_default();
+        curve.assert_valid();
+        if !self.is_admin(caller_id.clone()) {
+            require!(
+                caller_id == &owner_id,
+                "Only admins can create a coin for another account"
+            );
+            require!(
+                !self.reserved_names.contains(&name),
+                format!("Name {} is reserved", name)
+            );
+            require!(
+                !self.internal_has_active_coin(&owner_id),
+                format!("Account @{} already has a creator coin", owner_id)
+            );
+        }
+        require!(
+            admin_public_key.is_none() || caller_id == &owner_id,
+            "Only the owner can add an admin key to their coin"
+        );
+        require!(
+            self.symbols.insert(&symbol),
+            format!("Symbol {} is already taken", symbol)
+        );
+        let args = CreatorCoinArgs {
+            creator_id: owner_id,
+            reserve_token_id: get_lzr_token_contract(),
+            metadata: FungibleTokenMetadata {
+                spec: FT_METADATA_SPEC.to_string(),
+                name: token_name,
+                symbol: symbol.clone(),
+                icon,
+                reference: None,
+                reference_hash: None,
+                decimals: TOKEN_DECIMAL,
+            },
+            curve,
+        };
+        let (code_version, code) = self.internal_default_code();
+        let mut required_deposit = coin_storage_deposit(code.len(), &args.metadata);
+        if admin_public_key.is_some() {
+            required_deposit += ADMIN_KEY_ALLOWANCE;
+        }
+        require!(
+            available_deposit >= required_deposit,
+            format!("Must attach at least {} yoctoNEAR", required_deposit)
+        );
+        self.pending_owners.insert(&args.creator_id);
+
+        let mut promise = Promise::new(creator_coin_account_id.clone())
+            .create_account()
+            .transfer(required_deposit);
+        if let Some(public_key) = admin_public_key {
+            promise = promise.add_access_key(
+                public_key,
+                ADMIN_KEY_ALLOWANCE,
+                creator_coin_account_id.clone(),
+                CREATOR_ADMIN_METHODS.to_string(),
+            );
+        }
+        let promise = promise.deploy_contract(code).function_call(
+            CREATOR_COIN_INIT_METHOD.into(),
+            args.to_init_payload(),
+            NO_DEPOSIT,
+            Gas(CREATE_CALL_GAS),
+        );
+        (
+            promise,
+            PendingCoin {
+                coin_account_id: creator_coin_account_id,
+                owner_id: args.creator_id,
+                symbol,
+                code_version: code_version.into(),
+                forwarded_deposit: required_deposit.into(),
+            },
+        )
+    }
+
+    /// Registers `coin` if its creation succeeded, otherwise releases its symbol.
+    pub(crate) fn internal_resolve_create(&mut self, coin: PendingCoin) -> bool {
+        self.pending_owners.remove(&coin.owner_id);
+        if !is_promise_success() {
+            self.symbols.remove(&coin.symbol);
+            return false;
+        }
+        self.internal_register_coin(
+            coin.coin_account_id,
+            coin.owner_id,
+            coin.symbol,
+            coin.code_version,
+        );
+        true
+    }
+
+    fn assert_admin(&self) {
+        require!(
+            self.is_admin(env::predecessor_account_id()),
+            "Only admins can call this method"
+        );
+    }
+
+    /// An owner counts as having a coin while one is active or still being created.
+    fn internal_has_active_coin(&self, owner_id: &AccountId) -> bool {
+        self.pending_owners.contains(owner_id)
+            || self
+                .coins_by_owner
+                .get(owner_id)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|account_id| self.coins.get(account_id))
+                .any(|coin| coin.status == CoinStatus::Active)
+    }
+
+    fn internal_register_coin(
+        &mut self,
+        coin_account_id: AccountId,
+        owner_id: AccountId,
+        symbol: String,
+        code_version: Base58CryptoHash,
+    ) {
+        self.coins.insert(
+            &coin_account_id,
+            &CoinInfo {
+                owner_id: owner_id.clone(),
+                symbol,
+                created_at: env::block_timestamp().into(),
+                code_version,
+                status: CoinStatus::Active,
+            },
+        );
+        let mut owner_coins = self.coins_by_owner.get(&owner_id).unwrap_or_default();
+        owner_coins.push(coin_account_id);
+        self.coins_by_owner.insert(&owner_id, &owner_coins);
+    }
+}
+
+/// Part of `forwarded_deposit` to refund once the creation promise resolved.
+/// A failed creation is rolled back and the runtime returns the deposit it was sent,
+/// a successful one keeps it as the coin's storage.
+pub(crate) fn returned_deposit(forwarded_deposit: Balance) -> Balance {
+    match env::promise_result(0) {
+        PromiseResult::Failed => forwarded_deposit,
+        _ => 0,
+    }
+}
+
+/// NEAR a new coin account needs to pay for its code and state.
+fn coin_storage_deposit(code_size: usize, metadata: &FungibleTokenMetadata) -> Balance {
+    let metadata_size = metadata.try_to_vec().unwrap().len() as u64;
+    let storage = code_size as u64 + metadata_size + COIN_BASE_STORAGE;
+    env::storage_byte_cost() * Balance::from(storage)
+}
+
+/// Coin names become a direct sub-account of the factory, so they follow NEAR account rules
+/// and cannot contain dots. Returns the coin's account ID.
+fn assert_valid_coin_name(name: &str) -> AccountId {
+    require!(
+        name.len() >= 2 && !name.contains('.'),
+        "Name must be at least 2 characters and cannot contain dots"
+    );
+    format!("{}.{}", name, env::current_account_id())
+        .parse()
+        .unwrap_or_else(|_| env::panic_str("Name is not a valid NEAR account name"))
+}
+
+/// Symbols are short tickers of uppercase letters and digits that start with a letter.
+fn assert_valid_symbol(symbol: &str) {
+    require!(
+        !symbol.is_empty() && symbol.len() <= MAX_SYMBOL_LENGTH,
+        format!("Symbol must be 1 to {} characters", MAX_SYMBOL_LENGTH)
+    );
+    require!(
+        symbol.starts_with(|c: char| c.is_ascii_uppercase())
+            && symbol
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()),
+        "Symbol must be uppercase letters and digits, starting with a letter"
+    );
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+    use near_sdk::mock::VmAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts, VMContextBuilder};
+    use near_sdk::{testing_env, PromiseResult, RuntimeFeesConfig, VMConfig};
+
+    use super::*;
+
+    const CREATE_DEPOSIT: Balance = 10_000_000_000_000_000_000_000_000;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    // NEAR sent to `account_id` by the receipts of the last call.
+    fn transferred_to(account_id: &AccountId) -> Balance {
+        get_created_receipts()
+            .into_iter()
+            .filter(|receipt| &receipt.receiver_id == account_id)
+            .flat_map(|receipt| receipt.actions)
+            .map(|action| match action {
+                VmAction::Transfer { deposit } => deposit,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    // A factory with a stand-in coin wasm uploaded as its default version.
+    fn setup_factory() -> (CreatorCoinFactory, Base58CryptoHash) {
+        let mut context = get_context(accounts(0))
+            .attached_deposit(CREATE_DEPOSIT)
+            .build();
+        context.input = b"coin wasm".to_vec();
+        testing_env!(context);
+        let mut factory = CreatorCoinFactory::default();
+        let version = factory.upload_code();
+        factory.set_default_version(version);
+        (factory, version)
+    }
+
+    #[test]
+    fn test_migrate() {
+        testing_env!(get_context(accounts(0)).build());
+        env::state_write(&());
+
+        let factory = CreatorCoinFactory::migrate();
+        if !factory.get_coins(None, None).is_empty() || factory.get_creation_fee().is_some() {
+            env::panic_str("STATE NOT MIGRATED");
+        }
+    }
+
+    #[test]
+    fn test_create_reserves_symbol() {
+        let mut context = get_context(accounts(1));
+        let (mut factory, _) = setup_factory();
+        testing_env!(context.attached_deposit(CREATE_DEPOSIT).build());
+
+        factory.create(
+            accounts(1),
+            "burna".to_string(),
+            "Burna Coin".to_string(),
+            "BURNA".to_string(),
+            None,
+            None,
+            None,
+        );
+        if factory.is_symbol_available("BURNA".to_string()) {
+            env::panic_str("SYMBOL NOT RESERVED");
+        }
+        if !factory.is_symbol_available("WIZ".to_string()) {
+            env::panic_str("UNUSED SYMBOL NOT AVAILABLE");
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let context = get_context(accounts(0));
+        testing_env!(
+            context.build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])],
+        );
+        let mut factory = CreatorCoinFactory::default();
+        let coin_account_id: AccountId = "burna.factory.testnet".parse().unwrap();
+
+        factory.on_coin_create(
+            accounts(1),
+            PendingCoin {
+                coin_account_id: coin_account_id.clone(),
+                owner_id: accounts(1),
+                symbol: "BURNA".to_string(),
+                code_version: env::sha256_array(b"coin wasm").into(),
+                forwarded_deposit: 0.into(),
+            },
+        );
+        let coin = factory.get_coin(coin_account_id.clone()).unwrap();
+        if coin.owner_id != accounts(1) || coin.status != CoinStatus::Active {
+            env::panic_str("COIN NOT REGISTERED");
+        }
+        if factory.get_coins(None, None).len() != 1
+            || factory.get_coins(Some(0.into()), Some(1))[0].0 != coin_account_id
+            || !factory.get_coins(Some(1.into()), None).is_empty()
+        {
+            env::panic_str("INCORRECT COIN COUNT");
+        }
+        if factory.get_coins_by_owner(accounts(1))[0].0 != coin_account_id {
+            env::panic_str("COIN NOT INDEXED BY OWNER");
+        }
+    }
+
+    #[test]
+    fn test_update_coin_symbol() {
+        let mut context = get_context(accounts(0));
+        testing_env!(
+            context.build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])],
+        );
+        let mut factory = CreatorCoinFactory::default();
+        let coin_account_id: AccountId = "burna.factory.testnet".parse().unwrap();
+        factory.on_coin_create(
+            accounts(1),
+            PendingCoin {
+                coin_account_id: coin_account_id.clone(),
+                owner_id: accounts(1),
+                symbol: "BURNA".to_string(),
+                code_version: env::sha256_array(b"coin wasm").into(),
+                forwarded_deposit: 0.into(),
+            },
+        );
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        factory.update_coin_symbol(coin_account_id.clone(), "ODG".to_string());
+        if factory.is_symbol_available("ODG".to_string()) {
+            env::panic_str("NEW SYMBOL NOT RESERVED");
+        }
+
+        testing_env!(
+            context.predecessor_account_id(accounts(0)).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])],
+        );
+        factory.on_coin_symbol_updated(
+            coin_account_id.clone(),
+            "ODG".to_string(),
+            accounts(1),
+            0.into(),
+        );
+        if factory.get_coin(coin_account_id).unwrap().symbol != "ODG"
+            || !factory.is_symbol_available("BURNA".to_string())
+        {
+            env::panic_str("SYMBOL NOT CHANGED");
+        }
+    }
+
+    #[test]
+    fn test_one_coin_per_owner() {
+        let mut context = get_context(accounts(1));
+        let (mut factory, _) = setup_factory();
+        testing_env!(context.attached_deposit(CREATE_DEPOSIT).build());
+
+        factory.create(
+            accounts(1),
+            "burna".to_string(),
+            "Burna Coin".to_string(),
+            "BURNA".to_string(),
+            None,
+            None,
+            None,
+        );
+        if !factory.internal_has_active_coin(&accounts(1)) {
+            env::panic_str("PENDING COIN NOT COUNTED");
+        }
+        if factory.internal_has_active_coin(&accounts(2)) {
+            env::panic_str("OWNER WITHOUT COIN COUNTED");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Only admins can create a coin for another account")]
+    fn test_create_for_another_account() {
+        let mut context = get_context(accounts(2));
+        let (mut factory, _) = setup_factory();
+        testing_env!(context.attached_deposit(CREATE_DEPOSIT).build());
+
+        factory.create(
+            accounts(1),
+            "burna".to_string(),
+            "Burna Coin".to_string(),
+            "BURNA".to_string(),
+            None,
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Must attach at least")]
+    fn test_create_rejects_small_deposit() {
+        let mut context = get_context(accounts(1));
+        let (mut factory, _) = setup_factory();
+        testing_env!(context.attached_deposit(1).build());
+
+        factory.create(
+            accounts(1),
+            "burna".to_string(),
+            "Burna Coin".to_string(),
+            "BURNA".to_string(),
+            None,
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    fn test_create_refunds() {
+        let mut context = get_context(accounts(1));
+        let (mut factory, _) = setup_factory();
+        testing_env!(context.attached_deposit(CREATE_DEPOSIT).build());
+        let required: Balance = factory
+            .get_create_deposit("Burna Coin".to_string(), "BURNA".to_string(), None)
+            .into();
+
+        factory.create(
+            accounts(1),
+            "burna".to_string(),
+            "Burna Coin".to_string(),
+            "BURNA".to_string(),
+            None,
+            None,
+            None,
+        );
+        if transferred_to(&accounts(1)) != CREATE_DEPOSIT - required {
+            env::panic_str("EXCESS DEPOSIT NOT REFUNDED");
+        }
+
+        testing_env!(
+            context.predecessor_account_id(accounts(0)).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed],
+        );
+        let created = factory.on_coin_create(
+            accounts(1),
+            PendingCoin {
+                coin_account_id: "burna.alice".parse().unwrap(),
+                owner_id: accounts(1),
+                symbol: "BURNA".to_string(),
+                code_version: env::sha256_array(b"coin wasm").into(),
+                forwarded_deposit: required.into(),
+            },
+        );
+        if created || transferred_to(&accounts(1)) != required {
+            env::panic_str("FORWARDED DEPOSIT NOT REFUNDED");
+        }
+        if !factory.is_symbol_available("BURNA".to_string()) {
+            env::panic_str("SYMBOL NOT RELEASED");
+        }
+    }
+
+    #[test]
+    fn test_create_deposit() {
+        let context = get_context(accounts(1));
+        let (factory, _) = setup_factory();
+        testing_env!(context.build());
+
+        let plain: Balance = factory
+            .get_create_deposit("Burna Coin".to_string(), "BURNA".to_string(), None)
+            .into();
+        let with_icon: Balance = factory
+            .get_create_deposit(
+                "Burna Coin".to_string(),
+                "BURNA".to_string(),
+                Some("data:image/svg+xml,<svg></svg>".to_string()),
+            )
+            .into();
+        if plain < env::storage_byte_cost() * b"coin wasm".len() as Balance {
+            env::panic_str("DEPOSIT DOES NOT COVER CODE");
+        }
+        if plain > CREATE_DEPOSIT || with_icon <= plain {
+            env::panic_str("INCORRECT CREATE DEPOSIT");
+        }
+    }
+
+    #[test]
+    fn test_upgrade_coins() {
+        let context = get_context(accounts(0));
+        let (mut factory, old_version) = setup_factory();
+        testing_env!(context.build());
+        for name in ["burna", "wizkid", "tems"] {
+            let coin_account_id: AccountId = format!("{}.factory.testnet", name).parse().unwrap();
+            factory.internal_register_coin(
+                coin_account_id,
+                accounts(1),
+                name.to_uppercase(),
+                old_version,
+            );
+        }
+
+        let mut upload_context = get_context(accounts(0))
+            .attached_deposit(CREATE_DEPOSIT)
+            .build();
+        upload_context.input = b"coin wasm v2".to_vec();
+        testing_env!(upload_context);
+        let new_version = factory.upload_code();
+        if factory.get_code_versions().len() != 2 {
+            env::panic_str("CODE VERSION NOT STORED");
+        }
+
+        testing_env!(context.build());
+        let progress = factory.upgrade_coins(new_version, 2);
+        if progress.next_index.0 != 2 || progress.requested != 2 {
+            env::panic_str("INCORRECT FIRST BATCH");
+        }
+        let progress = factory.upgrade_coins(new_version, 2);
+        if progress.next_index.0 != 3 || progress.requested != 3 {
+            env::panic_str("INCORRECT SECOND BATCH");
+        }
+
+        testing_env!(
+            context.build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])],
+        );
+        let burna: AccountId = "burna.factory.testnet".parse().unwrap();
+        factory.on_coin_upgrade(burna.clone(), new_version, true);
+        if factory.get_coin(burna).unwrap().code_version != new_version {
+            env::panic_str("CODE VERSION NOT RECORDED");
+        }
+        if factory.get_upgrade_progress().unwrap().upgraded != 1 {
+            env::panic_str("UPGRADE NOT COUNTED");
+        }
+    }
+
+    #[test]
+    fn test_creation_fee() {
+        let mut context = get_context(accounts(0));
+        let (mut factory, _) = setup_factory();
+        testing_env!(context.build());
+        let fee: Balance = 100_000_000_000_000_000_000_000;
+        factory.set_creation_fee(Some(CreationFee {
+            amount: fee.into(),
+            treasury_id: accounts(3),
+        }));
+
+        testing_env!(context
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(CREATE_DEPOSIT)
+            .build());
+        factory.deposit_create_funds(None);
+        let funds: Balance = factory.create_funds_of(accounts(1)).into();
+
+        testing_env!(context
+            .predecessor_account_id(get_lzr_token_contract())
+            .attached_deposit(0)
+            .build());
+        let msg = r#"{"create": {"owner_id": "bob", "name": "burna", "token_name": "Burna Coin", "symbol": "BURNA"}}"#;
+        factory.ft_on_transfer(accounts(1), (fee * 3).into(), msg.to_string());
+        if factory.is_symbol_available("BURNA".to_string()) {
+            env::panic_str("SYMBOL NOT RESERVED");
+        }
+        let forwarded_deposit = funds - Balance::from(factory.create_funds_of(accounts(1)));
+        if forwarded_deposit == 0 {
+            env::panic_str("STORAGE NOT PAID FROM PREPAID FUNDS");
+        }
+
+        testing_env!(
+            context.predecessor_account_id(accounts(0)).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])],
+        );
+        let unused = factory.on_coin_create_with_fee(
+            accounts(1),
+            PendingCoin {
+                coin_account_id: "burna.alice".parse().unwrap(),
+                owner_id: accounts(1),
+                symbol: "BURNA".to_string(),
+                code_version: env::sha256_array(b"coin wasm").into(),
+                forwarded_deposit: forwarded_deposit.into(),
+            },
+            (fee * 3).into(),
+            factory.get_creation_fee(),
+        );
+        if unused.0 != fee * 2 {
+            env::panic_str("FEE NOT KEPT");
+        }
+        if factory.get_coins_by_owner(accounts(1)).len() != 1 {
+            env::panic_str("COIN NOT REGISTERED");
+        }
+    }
+
+    #[test]
+    fn test_withdraw_lzr() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut factory = CreatorCoinFactory::default();
+        testing_env!(context.attached_deposit(1).build());
+
+        factory.withdraw_lzr(accounts(3), 100.into());
+        let withdrawn = get_created_receipts().into_iter().any(|receipt| {
+            receipt.receiver_id == get_lzr_token_contract()
+                && receipt.actions.iter().any(|action| {
+                    matches!(action, VmAction::FunctionCall { function_name, .. } if function_name == "ft_transfer")
+                })
+        });
+        if !withdrawn {
+            env::panic_str("LZR NOT WITHDRAWN");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Only admins can call this method")]
+    fn test_withdraw_lzr_by_non_admin() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut factory = CreatorCoinFactory::default();
+        testing_env!(context.attached_deposit(1).build());
+
+        factory.withdraw_lzr(accounts(1), 100.into());
+    }
+
+    #[test]
+    fn test_buy_router() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut factory = CreatorCoinFactory::default();
+        let coin_account_id: AccountId = "burna.factory.testnet".parse().unwrap();
+        factory.internal_register_coin(
+            coin_account_id.clone(),
+            accounts(1),
+            "BURNA".to_string(),
+            env::sha256_array(b"coin wasm").into(),
+        );
+
+        testing_env!(context
+            .predecessor_account_id(get_lzr_token_contract())
+            .build());
+        let msg = format!(r#"{{"buy": "{}", "min_out": "1"}}"#, coin_account_id);
+        factory.ft_on_transfer(accounts(2), 1000.into(), msg);
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        if factory.on_buy_routed(Ok(600.into()), 1000.into()).0 != 400 {
+            env::panic_str("INCORRECT UNUSED AMOUNT");
+        }
+        if factory
+            .on_buy_routed(Err(near_sdk::PromiseError::Failed), 1000.into())
+            .0
+            != 1000
+        {
+            env::panic_str("FAILED BUY NOT REFUNDED");
         }
-        Promise::new(predecessor_account_id).transfer(attached_deposit.0);
-        return false;
     }
 }
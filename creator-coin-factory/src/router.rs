@@ -0,0 +1,102 @@
+use crate::*;
+use creator_coin_common::BuyMessage;
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+
+const ROUTE_BUY_GAS: u64 = 100_000_000_000_000;
+const ON_BUY_ROUTED_GAS: u64 = 10_000_000_000_000;
+
+/// `msg` of an LZR `ft_transfer_call` to the factory.
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde", untagged)]
+enum TransferMessage {
+    /// `{"create": {...}}` creates a coin, paying the creation fee out of the transfer.
+    Create { create: CreateArgs },
+    /// `{"buy": "artist.factory", "min_out": "...", "referrer_id": "..."}` buys a registered
+    /// coin with the transfer.
+    Buy {
+        buy: AccountId,
+        min_out: Option<U128>,
+        referrer_id: Option<AccountId>,
+    },
+}
+
+#[near_bindgen]
+impl CreatorCoinFactory {
+    /// Callback after LZR was forwarded to a coin's buy path.
+    /// Returns how much of the original `amount` went unused.
+    pub fn on_buy_routed(
+        &mut self,
+        #[callback_result] used: Result<U128, near_sdk::PromiseError>,
+        amount: U128,
+    ) -> U128 {
+        assert_self();
+
+        match used {
+            Ok(used) => (amount.0 - used.0.min(amount.0)).into(),
+            Err(_) => amount,
+        }
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenReceiver for CreatorCoinFactory {
+    /// Accepts LZR only. Unused LZR is returned through the transfer call resolution.
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        require!(
+            env::predecessor_account_id() == get_lzr_token_contract(),
+            "Only LZR is accepted"
+        );
+        let message: TransferMessage = near_sdk::serde_json::from_str(&msg)
+            .unwrap_or_else(|_| env::panic_str("Unsupported message"));
+
+        match message {
+            TransferMessage::Create { create } => {
+                self.internal_create_with_fee(sender_id, amount, create)
+            }
+            TransferMessage::Buy {
+                buy,
+                min_out,
+                referrer_id,
+            } => self.internal_route_buy(sender_id, amount, buy, min_out, referrer_id),
+        }
+    }
+}
+
+impl CreatorCoinFactory {
+    /// Forwards `amount` LZR to the buy path of `coin_account_id`, minting to `sender_id`.
+    fn internal_route_buy(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        coin_account_id: AccountId,
+        min_out: Option<U128>,
+        referrer_id: Option<AccountId>,
+    ) -> PromiseOrValue<U128> {
+        let coin = self
+            .coins
+            .get(&coin_account_id)
+            .unwrap_or_else(|| env::panic_str("Unknown coin"));
+        require!(coin.status == CoinStatus::Active, "Coin is not active");
+
+        let msg = BuyMessage {
+            receiver_id: Some(sender_id),
+            min_out,
+            referrer_id,
+        };
+        ext_ft_transfer::ext(get_lzr_token_contract())
+            .with_attached_deposit(1)
+            .with_static_gas(Gas(ROUTE_BUY_GAS))
+            .ft_transfer_call(coin_account_id, amount, None, msg.to_msg())
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(Gas(ON_BUY_ROUTED_GAS))
+                    .on_buy_routed(amount),
+            )
+            .into()
+    }
+}
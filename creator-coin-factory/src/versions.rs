@@ -0,0 +1,184 @@
+use crate::*;
+use creator_coin_common::CREATOR_COIN_UPDATE_METHOD;
+
+const UPDATE_CALL_GAS: u64 = 60_000_000_000_000;
+const ON_UPDATE_CALL_GAS: u64 = 10_000_000_000_000;
+
+/// Where the current batch upgrade stands, so `upgrade_coins` can pick up on the next call.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UpgradeProgress {
+    pub version: Base58CryptoHash,
+    /// Registry index the next batch starts at.
+    pub next_index: U64,
+    /// Coins an upgrade was sent to.
+    pub requested: u64,
+    pub upgraded: u64,
+    pub failed: u64,
+}
+
+#[near_bindgen]
+impl CreatorCoinFactory {
+    /// Stores the creator coin wasm passed as raw input and returns its sha256.
+    /// The attached deposit pays for the code's storage.
+    #[payable]
+    pub fn upload_code(&mut self) -> Base58CryptoHash {
+        self.assert_admin();
+        let code = env::input().unwrap_or_else(|| env::panic_str("Missing code"));
+        let version = env::sha256_array(&code);
+
+        let initial_storage_usage = env::storage_usage();
+        self.codes.insert(&version, &code);
+        let required_cost = env::storage_byte_cost()
+            * Balance::from(env::storage_usage().saturating_sub(initial_storage_usage));
+        require!(
+            env::attached_deposit() >= required_cost,
+            format!("Must attach {} yoctoNEAR to cover storage", required_cost)
+        );
+        let refund = env::attached_deposit() - required_cost;
+        if refund > 0 {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+
+        let version = Base58CryptoHash::from(version);
+        log!("Uploaded code version {}", String::from(&version));
+        version
+    }
+
+    /// Sets the code version new coins are created with.
+    pub fn set_default_version(&mut self, version: Base58CryptoHash) {
+        self.assert_admin();
+        let version = CryptoHash::from(version);
+        require!(self.codes.get(&version).is_some(), "Unknown code version");
+        self.default_version = Some(version);
+    }
+
+    pub fn get_default_version(&self) -> Option<Base58CryptoHash> {
+        self.default_version.map(Base58CryptoHash::from)
+    }
+
+    pub fn get_code_versions(&self) -> Vec<Base58CryptoHash> {
+        self.codes.keys().map(Base58CryptoHash::from).collect()
+    }
+
+    /// Deploys `version` to `coin_account_id` and migrates the coin's state.
+    /// The coin's owner must have approved the version with `approve_code_update` first.
+    /// Callable by the coin's owner or an admin.
+    pub fn upgrade_coin(
+        &mut self,
+        coin_account_id: AccountId,
+        version: Base58CryptoHash,
+    ) -> Promise {
+        let coin = self
+            .coins
+            .get(&coin_account_id)
+            .unwrap_or_else(|| env::panic_str("Coin not found"));
+        let predecessor_id = env::predecessor_account_id();
+        require!(
+            predecessor_id == coin.owner_id || self.is_admin(predecessor_id),
+            "Only the coin owner or an admin can upgrade a coin"
+        );
+        let code = self.internal_unwrap_code(&version.into());
+        internal_upgrade_coin(coin_account_id, version, code, false)
+    }
+
+    /// Upgrades up to `limit` registered coins to `version`, continuing where the previous
+    /// call for the same version stopped. Coins already running `version` are skipped.
+    /// Starting a different version resets the progress.
+    pub fn upgrade_coins(&mut self, version: Base58CryptoHash, limit: u64) -> UpgradeProgress {
+        self.assert_admin();
+        let code = self.internal_unwrap_code(&version.into());
+        let mut progress = match self.upgrade_progress.clone() {
+            Some(progress) if progress.version == version => progress,
+            _ => UpgradeProgress {
+                version,
+                next_index: 0.into(),
+                requested: 0,
+                upgraded: 0,
+                failed: 0,
+            },
+        };
+
+        let coin_account_ids = self.coins.keys_as_vector();
+        let end = coin_account_ids.len().min(progress.next_index.0 + limit);
+        for index in progress.next_index.0..end {
+            let coin_account_id = coin_account_ids.get(index).unwrap();
+            if self.coins.get(&coin_account_id).unwrap().code_version == version {
+                continue;
+            }
+            internal_upgrade_coin(coin_account_id, version, code.clone(), true);
+            progress.requested += 1;
+        }
+        progress.next_index = end.into();
+        self.upgrade_progress = Some(progress.clone());
+        progress
+    }
+
+    /// Callback after a coin upgrade. Records the coin's new code version on success
+    /// and counts the result towards the batch upgrade if it was part of one.
+    pub fn on_coin_upgrade(
+        &mut self,
+        coin_account_id: AccountId,
+        version: Base58CryptoHash,
+        batch: bool,
+    ) -> bool {
+        assert_self();
+
+        let upgraded = is_promise_success();
+        if upgraded {
+            if let Some(mut coin) = self.coins.get(&coin_account_id) {
+                coin.code_version = version;
+                self.coins.insert(&coin_account_id, &coin);
+            }
+        }
+        if let Some(progress) = self.upgrade_progress.as_mut() {
+            if batch && progress.version == version {
+                if upgraded {
+                    progress.upgraded += 1;
+                } else {
+                    progress.failed += 1;
+                }
+            }
+        }
+        upgraded
+    }
+
+    pub fn get_upgrade_progress(&self) -> Option<UpgradeProgress> {
+        self.upgrade_progress.clone()
+    }
+}
+
+impl CreatorCoinFactory {
+    pub(crate) fn internal_default_code(&self) -> (CryptoHash, Vec<u8>) {
+        let version = self
+            .default_version
+            .unwrap_or_else(|| env::panic_str("No default code version"));
+        (version, self.internal_unwrap_code(&version))
+    }
+
+    fn internal_unwrap_code(&self, version: &CryptoHash) -> Vec<u8> {
+        self.codes
+            .get(version)
+            .unwrap_or_else(|| env::panic_str("Unknown code version"))
+    }
+}
+
+fn internal_upgrade_coin(
+    coin_account_id: AccountId,
+    version: Base58CryptoHash,
+    code: Vec<u8>,
+    batch: bool,
+) -> Promise {
+    Promise::new(coin_account_id.clone())
+        .function_call(
+            CREATOR_COIN_UPDATE_METHOD.into(),
+            code,
+            NO_DEPOSIT,
+            Gas(UPDATE_CALL_GAS),
+        )
+        .then(
+            CreatorCoinFactory::ext(env::current_account_id())
+                .with_static_gas(Gas(ON_UPDATE_CALL_GAS))
+                .on_coin_upgrade(coin_account_id, version, batch),
+        )
+}
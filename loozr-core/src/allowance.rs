@@ -0,0 +1,78 @@
+//! Allowance extension to NEP-141, so contracts can pull LZR from an account instead of
+//! waiting for an `ft_transfer_call`. The allowance state and checks are shared with the
+//! creator coins, see [`creator_coin_common::allowance`].
+
+use crate::*;
+use near_sdk::json_types::U64;
+
+#[near_bindgen]
+impl Contract {
+    /// Lets `spender_id` transfer up to `amount` of the caller's LZR until `expires_at`,
+    /// see [`Allowances::approve`].
+    #[payable]
+    pub fn ft_approve(&mut self, spender_id: AccountId, amount: U128, expires_at: Option<U64>) {
+        let owner_id = env::predecessor_account_id();
+        self.allowances.approve(
+            &owner_id,
+            &spender_id,
+            amount.into(),
+            expires_at.map(u64::from),
+        );
+        emit_event(
+            "ft_approve",
+            ApproveEvent {
+                owner_id: &owner_id,
+                spender_id: &spender_id,
+                amount,
+                expires_at,
+            },
+        );
+    }
+
+    /// Removes the allowance of `spender_id` and refunds its storage to the caller.
+    #[payable]
+    pub fn ft_revoke(&mut self, spender_id: AccountId) {
+        let owner_id = env::predecessor_account_id();
+        self.allowances.revoke(&owner_id, &spender_id);
+        emit_event(
+            "ft_revoke",
+            ApproveEvent {
+                owner_id: &owner_id,
+                spender_id: &spender_id,
+                amount: 0.into(),
+                expires_at: None,
+            },
+        );
+    }
+
+    /// LZR `spender_id` can still transfer from `owner_id`, 0 once the allowance expired.
+    pub fn ft_allowance(&self, owner_id: AccountId, spender_id: AccountId) -> U128 {
+        self.allowances.allowance(&owner_id, &spender_id).into()
+    }
+
+    /// Transfers `amount` of `owner_id`'s LZR to `receiver_id` out of the caller's allowance.
+    #[payable]
+    pub fn ft_transfer_from(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+    ) {
+        near_sdk::assert_one_yocto();
+        let spender_id = env::predecessor_account_id();
+        self.allowances.spend(&owner_id, &spender_id, amount.into());
+        self.token
+            .internal_transfer(&owner_id, &receiver_id, amount.0, memo);
+
+        emit_event(
+            "ft_transfer_from",
+            TransferFromEvent {
+                owner_id: &owner_id,
+                spender_id: &spender_id,
+                receiver_id: &receiver_id,
+                amount,
+            },
+        );
+    }
+}
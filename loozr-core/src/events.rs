@@ -0,0 +1,17 @@
+use near_sdk::env;
+use near_sdk::serde::Serialize;
+use near_sdk::serde_json::json;
+
+const EVENT_STANDARD: &str = "loozr";
+const EVENT_VERSION: &str = "1.0.0";
+
+/// Logs a NEP-297 event for the LZR extensions beyond NEP-141.
+pub(crate) fn emit_event<T: Serialize>(event: &str, data: T) {
+    let event = json!({
+        "standard": EVENT_STANDARD,
+        "version": EVENT_VERSION,
+        "event": event,
+        "data": [data],
+    });
+    env::log_str(&format!("EVENT_JSON:{}", event));
+}
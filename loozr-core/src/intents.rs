@@ -0,0 +1,106 @@
+//! Signed intents, so a relayer can pay the gas for accounts that hold no NEAR.
+//! An account registers an ed25519 key once and then signs intents off-chain. Anyone can
+//! submit an intent with its signature to `execute_intent`, which checks the signature, the
+//! expiry and the nonce and then executes the intent as if the signer had called the contract.
+//!
+//! Keys, nonces and signature checks are shared with the creator coins, see
+//! [`creator_coin_common::intents`].
+
+use crate::*;
+use near_contract_standards::fungible_token::receiver::ext_ft_receiver;
+use near_contract_standards::fungible_token::resolver::ext_ft_resolver;
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{Gas, PublicKey};
+
+pub use creator_coin_common::intents::IntentKeyView;
+
+const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(5_000_000_000_000);
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde", tag = "type", rename_all = "snake_case")]
+pub enum IntentAction {
+    Transfer {
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+    },
+    /// Transfer followed by `ft_on_transfer` on the receiver, e.g. to buy a creator coin.
+    TransferCall {
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    },
+}
+
+pub type Intent = creator_coin_common::intents::Intent<IntentAction>;
+
+#[near_bindgen]
+impl Contract {
+    /// Registers the ed25519 key the caller signs intents with, see [`IntentKeys::register`].
+    #[payable]
+    pub fn register_intent_key(&mut self, public_key: PublicKey) {
+        self.intent_keys
+            .register(&env::predecessor_account_id(), public_key);
+    }
+
+    pub fn intent_key_of(&self, account_id: AccountId) -> Option<IntentKeyView> {
+        self.intent_keys.key_of(&account_id)
+    }
+
+    /// Executes `intent`, the JSON of an [`Intent`], on behalf of its signer.
+    /// `signature` is the signer's ed25519 signature over the bytes of `intent`.
+    pub fn execute_intent(
+        &mut self,
+        intent: String,
+        signature: Base64VecU8,
+    ) -> PromiseOrValue<U128> {
+        let intent = self.internal_verify_intent(&intent, &signature.0);
+        let signer_id = intent.signer_id;
+        match intent.action {
+            IntentAction::Transfer {
+                receiver_id,
+                amount,
+                memo,
+            } => {
+                self.token
+                    .internal_transfer(&signer_id, &receiver_id, amount.0, memo);
+                PromiseOrValue::Value(amount)
+            }
+            IntentAction::TransferCall {
+                receiver_id,
+                amount,
+                memo,
+                msg,
+            } => {
+                self.token
+                    .internal_transfer(&signer_id, &receiver_id, amount.0, memo);
+                ext_ft_receiver::ext(receiver_id.clone())
+                    .ft_on_transfer(signer_id.clone(), amount, msg)
+                    .then(
+                        ext_ft_resolver::ext(env::current_account_id())
+                            .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
+                            .ft_resolve_transfer(signer_id, receiver_id, amount),
+                    )
+                    .into()
+            }
+        }
+    }
+}
+
+impl Contract {
+    /// Verifies `intent` and spends its nonce, see [`IntentKeys::verify`].
+    fn internal_verify_intent(&mut self, intent: &str, signature: &[u8]) -> Intent {
+        let intent: Intent = self.intent_keys.verify(intent, signature);
+        emit_event(
+            "intent_executed",
+            IntentExecutedEvent {
+                signer_id: &intent.signer_id,
+                relayer_id: &env::predecessor_account_id(),
+                nonce: intent.nonce,
+            },
+        );
+        intent
+    }
+}
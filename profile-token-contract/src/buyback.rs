@@ -0,0 +1,81 @@
+//! Creator buybacks. The creator sends LZR with `ft_transfer_call` and `msg` set to
+//! `"buyback_and_burn"`. The LZR is added to the reserve as if it bought coins on the curve,
+//! and the coins it would have bought are burned straight away, so none are minted.
+//!
+//! Curve invariants: `lzr_locked` grows while `total_supply` stays the same, so the spot
+//! price `lzr_locked / (total_supply * reserve_ratio)` rises. Afterwards the reserve holds
+//! more than the curve's `reserve_ratio` implies for the supply. Sales still pay out
+//! `lzr_locked * (1 - (1 - p / x) ^ (1 / r))`, which never exceeds `lzr_locked`, so
+//! selling the whole supply still drains the reserve exactly and the coin stays solvent.
+//! Every holder's share of the reserve grows by the bought back amount.
+
+use crate::*;
+use near_sdk::serde::Serialize;
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BuybackStats {
+    /// LZR added to the reserve by buybacks.
+    pub lzr_spent: U128,
+    /// Coins the buybacks would have minted and burned instead.
+    pub coins_burned: U128,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct BuybackEvent<'a> {
+    owner_id: &'a AccountId,
+    lzr_spent: U128,
+    coins_burned: U128,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn buyback_stats(&self) -> BuybackStats {
+        BuybackStats {
+            lzr_spent: self.buyback_lzr_spent.into(),
+            coins_burned: self.buyback_coins_burned.into(),
+        }
+    }
+}
+
+impl Contract {
+    /// Adds `amount` LZR from the owner to the reserve without minting and returns the
+    /// coins burned in its place.
+    pub(crate) fn internal_buyback_and_burn(
+        &mut self,
+        sender_id: &AccountId,
+        amount: Balance,
+    ) -> Balance {
+        self.assert_not_wound_down();
+        require!(
+            *sender_id == self.owner_id,
+            "Only the owner can buy back and burn"
+        );
+        require!(amount > 0, "Amount must be non-zero.");
+        require!(
+            self.lzr_locked > 0 && self.token.total_supply > 0,
+            "Nothing to buy back"
+        );
+
+        self.internal_before_trade();
+        let burned = self.calc_purchase_return(amount);
+        self.lzr_locked = self
+            .lzr_locked
+            .checked_add(amount)
+            .unwrap_or_else(|| env::panic_str("Reserve balance overflow"));
+        self.internal_after_trade();
+        self.buyback_lzr_spent += amount;
+        self.buyback_coins_burned += burned;
+
+        emit_event(
+            "buyback",
+            BuybackEvent {
+                owner_id: sender_id,
+                lzr_spent: amount.into(),
+                coins_burned: burned.into(),
+            },
+        );
+        burned
+    }
+}
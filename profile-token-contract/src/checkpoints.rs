@@ -0,0 +1,234 @@
+use crate::*;
+use near_sdk::collections::Vector;
+use near_sdk::json_types::U64;
+use near_sdk::{BlockHeight, IntoStorageKey};
+
+/// Snapshots whose balances stay readable. Taking a newer one retires the oldest.
+pub const MAX_SNAPSHOTS: u64 = 15;
+/// Checkpoints kept per account, enough to answer every retained snapshot.
+pub const MAX_ACCOUNT_CHECKPOINTS: u64 = MAX_SNAPSHOTS + 1;
+
+/// Balance of an account, or the total supply, as of the end of `block_height`.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy)]
+pub struct Checkpoint {
+    pub block_height: BlockHeight,
+    pub balance: Balance,
+}
+
+/// A block whose balances holders polls and airdrops are based on.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Snapshot {
+    pub block_height: BlockHeight,
+    /// A newer snapshot cannot retire this one before then, so open polls keep their weights.
+    pub pinned_until: Timestamp,
+}
+
+/// Balance checkpoints of one account, kept in a ring so their storage stays bounded.
+/// A new checkpoint is only added once a snapshot was taken since the last one, so the
+/// ring always holds the balance at every retained snapshot however often it changes.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct CheckpointHistory {
+    checkpoints: Vector<Checkpoint>,
+    /// Slot the next new checkpoint overwrites once the ring is full.
+    next: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Takes a snapshot at the current block for an airdrop or anything else that needs
+    /// holder balances at a fixed point, see `ft_balance_of_at`. Returns its block height.
+    /// The attached deposit pays for the snapshot's storage.
+    #[payable]
+    pub fn take_snapshot(&mut self) -> U64 {
+        self.assert_owner();
+        let initial_storage_usage = env::storage_usage();
+        let block_height = self.internal_take_snapshot(0);
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+        block_height.into()
+    }
+
+    /// Block heights of the snapshots whose balances can still be read, oldest first.
+    pub fn retained_snapshots(&self) -> Vec<U64> {
+        (self.snapshots.len().saturating_sub(MAX_SNAPSHOTS)..self.snapshots.len())
+            .map(|index| self.snapshots.get(index).unwrap().block_height.into())
+            .collect()
+    }
+
+    /// Balance of `account_id` at the end of `block_height`, which must be one of the
+    /// [`MAX_SNAPSHOTS`] latest snapshots.
+    pub fn ft_balance_of_at(&self, account_id: AccountId, block_height: U64) -> U128 {
+        self.assert_retained_snapshot(block_height.into());
+        self.account_checkpoints
+            .get(&account_id)
+            .map(|history| history.balance_at(block_height.into()))
+            .unwrap_or(0)
+            .into()
+    }
+
+    /// Total supply at the end of `block_height`, which must be a retained snapshot.
+    pub fn ft_total_supply_at(&self, block_height: U64) -> U128 {
+        self.assert_retained_snapshot(block_height.into());
+        self.supply_checkpoints
+            .balance_at(block_height.into())
+            .into()
+    }
+}
+
+impl Contract {
+    /// Takes a snapshot at the current block that cannot be retired before `pinned_until`.
+    pub(crate) fn internal_take_snapshot(&mut self, pinned_until: Timestamp) -> BlockHeight {
+        let block_height = env::block_height();
+        let len = self.snapshots.len();
+        if let Some(mut last) = len
+            .checked_sub(1)
+            .map(|index| self.snapshots.get(index).unwrap())
+        {
+            if last.block_height == block_height {
+                last.pinned_until = last.pinned_until.max(pinned_until);
+                self.snapshots.replace(len - 1, &last);
+                return block_height;
+            }
+        }
+        if len >= MAX_SNAPSHOTS {
+            require!(
+                self.snapshots
+                    .get(len - MAX_SNAPSHOTS)
+                    .unwrap()
+                    .pinned_until
+                    <= env::block_timestamp(),
+                "The oldest retained snapshot is still used by an open poll"
+            );
+        }
+        self.snapshots.push(&Snapshot {
+            block_height,
+            pinned_until,
+        });
+        log!("Snapshot taken at block {}", block_height);
+        block_height
+    }
+
+    fn assert_retained_snapshot(&self, block_height: BlockHeight) {
+        let len = self.snapshots.len();
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let mid = (low + high) / 2;
+            if self.snapshots.get(mid).unwrap().block_height < block_height {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        require!(
+            low < len && self.snapshots.get(low).unwrap().block_height == block_height,
+            "No snapshot was taken at that block"
+        );
+        require!(
+            low + MAX_SNAPSHOTS >= len,
+            "Balance history for that snapshot is no longer retained"
+        );
+    }
+
+    fn latest_snapshot_block(&self) -> Option<BlockHeight> {
+        self.snapshots
+            .len()
+            .checked_sub(1)
+            .map(|index| self.snapshots.get(index).unwrap().block_height)
+    }
+
+    /// Records the current balance of `account_id` for the current block. The storage is
+    /// paid at registration, see [`Contract::measure_checkpoint_storage_usage`].
+    pub(crate) fn internal_checkpoint_account(&mut self, account_id: &AccountId) {
+        let balance = self.internal_unwrap_balance_of(account_id.clone());
+        let latest_snapshot = self.latest_snapshot_block();
+        let mut history = self.account_checkpoints.get(account_id).unwrap_or_else(|| {
+            CheckpointHistory::new(StorageKey::Checkpoints {
+                account_hash: env::sha256_array(account_id.as_bytes()),
+            })
+        });
+        history.push(balance, latest_snapshot);
+        self.account_checkpoints.insert(account_id, &history);
+    }
+
+    /// Frees the checkpoints of an account that closed its registration.
+    pub(crate) fn internal_remove_checkpoints(&mut self, account_id: &AccountId) {
+        if let Some(mut history) = self.account_checkpoints.remove(account_id) {
+            history.checkpoints.clear();
+        }
+    }
+
+    /// Records the current total supply for the current block. Paid for by the contract.
+    pub(crate) fn internal_checkpoint_supply(&mut self) {
+        let total_supply = self.token.total_supply;
+        let latest_snapshot = self.latest_snapshot_block();
+        self.supply_checkpoints.push(total_supply, latest_snapshot);
+    }
+
+    /// Adds the storage of a full checkpoint ring to what every account pays on registration.
+    pub(crate) fn measure_checkpoint_storage_usage(&mut self) {
+        let initial_storage_usage = env::storage_usage();
+        let tmp_account_id = AccountId::new_unchecked("a".repeat(64));
+        let mut history = CheckpointHistory::new(StorageKey::Checkpoints {
+            account_hash: env::sha256_array(tmp_account_id.as_bytes()),
+        });
+        for _ in 0..MAX_ACCOUNT_CHECKPOINTS {
+            history.checkpoints.push(&Checkpoint {
+                block_height: 0,
+                balance: 0,
+            });
+        }
+        self.account_checkpoints.insert(&tmp_account_id, &history);
+        self.token.account_storage_usage += env::storage_usage() - initial_storage_usage;
+        self.internal_remove_checkpoints(&tmp_account_id);
+    }
+}
+
+impl CheckpointHistory {
+    pub(crate) fn new<S: IntoStorageKey>(prefix: S) -> Self {
+        Self {
+            checkpoints: Vector::new(prefix),
+            next: 0,
+        }
+    }
+
+    /// Records `balance` for the current block. The latest checkpoint is overwritten unless
+    /// `latest_snapshot` falls between it and the current block.
+    fn push(&mut self, balance: Balance, latest_snapshot: Option<BlockHeight>) {
+        let checkpoint = Checkpoint {
+            block_height: env::block_height(),
+            balance,
+        };
+        let len = self.checkpoints.len();
+        if let Some(last) = len.checked_sub(1).map(|index| (self.next + index) % len) {
+            let last_block_height = self.checkpoints.get(last).unwrap().block_height;
+            let snapshot_since = latest_snapshot.is_some_and(|snapshot| {
+                last_block_height <= snapshot && snapshot < checkpoint.block_height
+            });
+            if !snapshot_since {
+                self.checkpoints.replace(last, &checkpoint);
+                return;
+            }
+        }
+        if len < MAX_ACCOUNT_CHECKPOINTS {
+            self.checkpoints.push(&checkpoint);
+        } else {
+            self.checkpoints.replace(self.next, &checkpoint);
+            self.next = (self.next + 1) % len;
+        }
+    }
+
+    /// Binary searches the ring, oldest checkpoint first, for the balance at `block_height`.
+    fn balance_at(&self, block_height: BlockHeight) -> Balance {
+        let len = self.checkpoints.len();
+        let get = |index: u64| self.checkpoints.get((self.next + index) % len).unwrap();
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let mid = (low + high) / 2;
+            if get(mid).block_height <= block_height {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low.checked_sub(1).map_or(0, |index| get(index).balance)
+    }
+}
@@ -0,0 +1,144 @@
+//! Circuit breaker for extreme price moves. The spot price at the first trade of a window
+//! is the reference. A trade that moves the spot price more than `max_move_bps` away from it
+//! within the window still settles, but halts trading for `cooldown` nanoseconds afterwards.
+
+use crate::*;
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::Timestamp;
+
+/// Spot prices are in yocto LZR per whole coin.
+const PRICE_PRECISION: u128 = 1_000_000_000_000_000_000_000_000;
+const BPS_DENOMINATOR: u128 = 10_000;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CircuitBreakerConfig {
+    /// Largest move away from the reference price in basis points before trading halts.
+    pub max_move_bps: u16,
+    /// Nanoseconds a reference price stays in use.
+    pub window: U64,
+    /// Nanoseconds trading stays halted once the breaker trips.
+    pub cooldown: U64,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CircuitBreakerView {
+    pub config: Option<CircuitBreakerConfig>,
+    pub reference_price: U128,
+    pub window_started_at: U64,
+    /// Trading is halted until this timestamp, 0 when it is not halted.
+    pub halted_until: U64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Sets the circuit breaker, or turns it off with `None`. Resets the current window.
+    pub fn set_circuit_breaker(&mut self, config: Option<CircuitBreakerConfig>) {
+        self.assert_owner();
+        if let Some(config) = config {
+            require!(
+                config.max_move_bps > 0 && config.window.0 > 0,
+                "Max move and window must be non-zero"
+            );
+        }
+        self.circuit_breaker = config;
+        self.reference_price = 0;
+        self.reference_price_at = 0;
+    }
+
+    /// Lifts a halt before its cooldown ends. The next trade starts a new window.
+    pub fn resume_trading(&mut self) {
+        self.assert_owner();
+        require!(self.is_trading_halted(), "Trading is not halted");
+        self.trading_halted_until = 0;
+        self.reference_price = 0;
+        self.reference_price_at = 0;
+        log!("Trading resumed by the owner");
+    }
+
+    pub fn circuit_breaker(&self) -> CircuitBreakerView {
+        CircuitBreakerView {
+            config: self.circuit_breaker,
+            reference_price: self.reference_price.into(),
+            window_started_at: self.reference_price_at.into(),
+            halted_until: self.trading_halted_until.into(),
+        }
+    }
+
+    pub fn is_trading_halted(&self) -> bool {
+        env::block_timestamp() < self.trading_halted_until
+    }
+
+    /// Marginal price of one whole coin in yocto LZR, `lzr_locked / (total_supply * reserve_ratio)`.
+    pub fn spot_price(&self) -> U128 {
+        self.internal_spot_price().into()
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_spot_price(&self) -> Balance {
+        spot_price(
+            self.lzr_locked,
+            self.token.total_supply,
+            self.curve.reserve_ratio,
+        )
+    }
+
+    /// Panics while trading is halted, and otherwise updates the price oracle and starts a
+    /// new window with the current spot price as reference once the previous one has expired.
+    /// Called before every trade.
+    pub(crate) fn internal_before_trade(&mut self) {
+        require!(!self.is_trading_halted(), "Trading is halted");
+        self.internal_update_price_oracle();
+        let config = match self.circuit_breaker {
+            Some(config) => config,
+            None => return,
+        };
+        let now = env::block_timestamp();
+        if self.reference_price == 0 || now >= self.reference_price_at + config.window.0 {
+            self.reference_price = self.internal_spot_price();
+            self.reference_price_at = now;
+        }
+    }
+
+    /// Trips the breaker if the trade just made moved the spot price too far.
+    /// The trade itself settles, so tripping the breaker always costs a real trade.
+    pub(crate) fn internal_after_trade(&mut self) {
+        let config = match self.circuit_breaker {
+            Some(config) => config,
+            None => return,
+        };
+        if self.reference_price == 0 {
+            return;
+        }
+        let price = self.internal_spot_price();
+        let move_bps = U256::from(price.abs_diff(self.reference_price))
+            * U256::from(BPS_DENOMINATOR)
+            / U256::from(self.reference_price);
+        if move_bps > U256::from(config.max_move_bps) {
+            let halted_until: Timestamp = env::block_timestamp() + config.cooldown.0;
+            self.trading_halted_until = halted_until;
+            self.reference_price = 0;
+            self.reference_price_at = 0;
+            log!(
+                "Price moved {} bps within the window, trading halted until {}",
+                move_bps,
+                halted_until
+            );
+        }
+    }
+}
+
+/// Spot price with `lzr_locked` in the reserve for `total_supply` coins.
+fn spot_price(lzr_locked: Balance, total_supply: Balance, reserve_ratio: f64) -> Balance {
+    if total_supply == 0 {
+        return 0;
+    }
+    let price: Balance = (U256::from(lzr_locked) * U256::from(PRICE_PRECISION)
+        / U256::from(total_supply))
+    .try_into()
+    .unwrap_or_else(|_| env::panic_str("Spot price overflow"));
+    (price as f64 / reserve_ratio) as u128
+}
@@ -0,0 +1,131 @@
+use crate::*;
+
+/// Scale applied to `dividend_per_share` so small distributions over a large supply still count.
+const DIVIDEND_PRECISION: u128 = 1_000_000_000_000_000_000_000_000;
+
+/// Dividend bookkeeping for one holder, settled whenever their balance changes.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct DividendAccount {
+    /// Value of `dividend_per_share` the last time this account was settled.
+    pub last_dividend_per_share: u128,
+    /// LZR earned up to the last settlement and not yet claimed.
+    pub unclaimed: Balance,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Sends the caller all LZR dividends they have earned so far.
+    #[payable]
+    pub fn claim_dividends(&mut self) -> Promise {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        self.internal_settle_dividends(&account_id);
+
+        let mut dividend_account = self.dividend_accounts.get(&account_id).unwrap_or_default();
+        let amount = dividend_account.unclaimed;
+        require!(amount > 0, "No dividends to claim");
+        dividend_account.unclaimed = 0;
+        self.dividend_accounts
+            .insert(&account_id, &dividend_account);
+
+        ext_ft_transfer::ext(self.reserve_token_id.clone())
+            .with_attached_deposit(1)
+            .ft_transfer(account_id.clone(), amount.into())
+            .then(
+                Self::ext(env::current_account_id())
+                    .on_dividends_claimed(account_id, amount.into()),
+            )
+    }
+
+    #[private]
+    pub fn on_dividends_claimed(
+        &mut self,
+        #[callback_result] call_result: Result<(), near_sdk::PromiseError>,
+        account_id: AccountId,
+        amount: U128,
+    ) -> U128 {
+        if call_result.is_err() {
+            let mut dividend_account = self.dividend_accounts.get(&account_id).unwrap_or_default();
+            dividend_account.unclaimed += amount.0;
+            self.dividend_accounts
+                .insert(&account_id, &dividend_account);
+            return 0.into();
+        }
+        log!("Account @{} claimed {} LZR dividends", account_id, amount.0);
+        amount
+    }
+
+    /// LZR dividends `account_id` could claim right now.
+    pub fn dividends_of(&self, account_id: AccountId) -> U128 {
+        let dividend_account = self.dividend_accounts.get(&account_id).unwrap_or_default();
+        let balance = self.internal_unwrap_balance_of(account_id);
+        (dividend_account.unclaimed + self.internal_pending_dividends(&dividend_account, balance))
+            .into()
+    }
+}
+
+impl Contract {
+    /// Shares `amount` LZR between every holder pro-rata to their current balance.
+    /// Coins held in escrow by the contract itself do not earn dividends.
+    pub(crate) fn internal_distribute_dividends(&mut self, sender_id: &AccountId, amount: Balance) {
+        require!(amount > 0, "Amount must be non-zero.");
+        let eligible_supply =
+            self.token.total_supply - self.internal_unwrap_balance_of(env::current_account_id());
+        require!(eligible_supply > 0, "No holders to distribute to");
+
+        let increment =
+            U256::from(amount) * U256::from(DIVIDEND_PRECISION) / U256::from(eligible_supply);
+        self.dividend_per_share = (U256::from(self.dividend_per_share) + increment)
+            .try_into()
+            .unwrap_or_else(|_| env::panic_str("Dividend per share overflow"));
+
+        log!(
+            "Account @{} distributed {} LZR to holders",
+            sender_id,
+            amount
+        );
+    }
+
+    /// Moves dividends earned since the last settlement into `unclaimed`.
+    /// Must run before every balance change of `account_id`.
+    pub(crate) fn internal_settle_dividends(&mut self, account_id: &AccountId) {
+        if *account_id == env::current_account_id() {
+            return;
+        }
+        // a missing entry already reads as settled while nothing was distributed
+        let mut dividend_account = self.dividend_accounts.get(account_id).unwrap_or_default();
+        if dividend_account.last_dividend_per_share == self.dividend_per_share {
+            return;
+        }
+
+        let balance = self.internal_unwrap_balance_of(account_id.clone());
+        dividend_account.unclaimed += self.internal_pending_dividends(&dividend_account, balance);
+        dividend_account.last_dividend_per_share = self.dividend_per_share;
+        self.dividend_accounts.insert(account_id, &dividend_account);
+    }
+
+    /// Drops the dividend bookkeeping of an unregistered account.
+    /// Dividends it had not claimed are forfeited.
+    pub(crate) fn internal_remove_dividend_account(&mut self, account_id: &AccountId) {
+        self.dividend_accounts.remove(account_id);
+    }
+
+    /// Adds the storage of a [`DividendAccount`] to what each account pays on registration.
+    pub(crate) fn measure_dividend_storage_usage(&mut self) {
+        let initial_storage_usage = env::storage_usage();
+        let tmp_account_id = AccountId::new_unchecked("a".repeat(64));
+        self.dividend_accounts
+            .insert(&tmp_account_id, &DividendAccount::default());
+        self.token.account_storage_usage += env::storage_usage() - initial_storage_usage;
+        self.dividend_accounts.remove(&tmp_account_id);
+    }
+
+    fn internal_pending_dividends(
+        &self,
+        dividend_account: &DividendAccount,
+        balance: Balance,
+    ) -> Balance {
+        let delta = self.dividend_per_share - dividend_account.last_dividend_per_share;
+        (U256::from(balance) * U256::from(delta) / U256::from(DIVIDEND_PRECISION)).as_u128()
+    }
+}
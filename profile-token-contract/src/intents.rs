@@ -0,0 +1,107 @@
+//! Gasless trading through signed intents. Fans register an ed25519 key with the coin and
+//! sign transfers, buys and sells off-chain, and a relayer submits them with `execute_intent`
+//! and pays the gas. Each intent names this coin, expires, and carries the signer's next
+//! nonce, so it runs at most once and only here.
+//!
+//! Keys, nonces and signature checks are shared with LZR, see [`creator_coin_common::intents`].
+//! Buys are paid from the signer's LZR credit. To buy with LZR held elsewhere, sign a
+//! transfer-call intent on the LZR contract with a buy message instead.
+
+use crate::*;
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::PublicKey;
+
+pub use creator_coin_common::intents::IntentKeyView;
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde", tag = "type", rename_all = "snake_case")]
+pub enum IntentAction {
+    Transfer {
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+    },
+    /// Buys coins with `amount` of the signer's LZR credit, see `lzr_credit_of`.
+    Buy { amount: U128, min_out: Option<U128> },
+    /// Sells `amount` coins on the curve and sends the LZR proceeds to the signer.
+    /// Proceeds that cannot be sent are credited to the signer instead.
+    Sell { amount: U128 },
+}
+
+pub type Intent = creator_coin_common::intents::Intent<IntentAction>;
+
+#[near_bindgen]
+impl Contract {
+    /// Registers the ed25519 key the caller signs intents with, see [`IntentKeys::register`].
+    #[payable]
+    pub fn register_intent_key(&mut self, public_key: PublicKey) {
+        self.intent_keys
+            .register(&env::predecessor_account_id(), public_key);
+    }
+
+    pub fn intent_key_of(&self, account_id: AccountId) -> Option<IntentKeyView> {
+        self.intent_keys.key_of(&account_id)
+    }
+
+    /// Executes `intent`, the JSON of an [`Intent`], for its signer and returns the coins
+    /// transferred or bought, or the LZR a sell paid out.
+    /// `signature` is the signer's ed25519 signature over the bytes of `intent`.
+    pub fn execute_intent(
+        &mut self,
+        intent: String,
+        signature: Base64VecU8,
+    ) -> PromiseOrValue<U128> {
+        let intent = self.internal_verify_intent(&intent, &signature.0);
+        let signer_id = intent.signer_id;
+        match intent.action {
+            IntentAction::Transfer {
+                receiver_id,
+                amount,
+                memo,
+            } => {
+                self.internal_transfer_from(&signer_id, &receiver_id, amount.0, memo);
+                PromiseOrValue::Value(amount)
+            }
+            IntentAction::Buy { amount, min_out } => {
+                let credit = self.lzr_credits.get(&signer_id).unwrap_or(0);
+                require!(credit >= amount.0, "Amount exceeds LZR credit");
+                self.lzr_credits.insert(&signer_id, &(credit - amount.0));
+                let minted = self.internal_buy(
+                    signer_id,
+                    amount.0,
+                    min_out.map(Balance::from).unwrap_or(0),
+                    None,
+                );
+                PromiseOrValue::Value(minted.into())
+            }
+            IntentAction::Sell { amount } => {
+                let proceeds = self.internal_sell(signer_id.clone(), amount.0);
+                ext_ft_transfer::ext(self.reserve_token_id.clone())
+                    .with_attached_deposit(1)
+                    .ft_transfer(signer_id.clone(), proceeds.into())
+                    .then(
+                        Self::ext(env::current_account_id())
+                            .on_lzr_credit_withdrawn(signer_id, proceeds.into()),
+                    )
+                    .into()
+            }
+        }
+    }
+}
+
+impl Contract {
+    /// Verifies `intent` and spends its nonce, see [`IntentKeys::verify`].
+    fn internal_verify_intent(&mut self, intent: &str, signature: &[u8]) -> Intent {
+        let intent: Intent = self.intent_keys.verify(intent, signature);
+        emit_event(
+            "intent_executed",
+            IntentExecutedEvent {
+                signer_id: &intent.signer_id,
+                relayer_id: &env::predecessor_account_id(),
+                nonce: intent.nonce,
+            },
+        );
+        intent
+    }
+}
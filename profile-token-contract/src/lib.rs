@@ -1,29 +1,123 @@
+use creator_coin_common::allowance::{Allowances, ApproveEvent, TransferFromEvent};
+use creator_coin_common::intents::{IntentExecutedEvent, IntentKeys};
+use creator_coin_common::{
+    refund_deposit, BuyMessage, CreatorCoinArgs, CreatorCoinInit, CurveParams, ReferralConfig,
+};
+use near_contract_standards::fungible_token::core::FungibleTokenCore;
 use near_contract_standards::fungible_token::metadata::{
     FungibleTokenMetadata, FungibleTokenMetadataProvider, FT_METADATA_SPEC,
 };
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+use near_contract_standards::fungible_token::resolver::FungibleTokenResolver;
 use near_contract_standards::fungible_token::FungibleToken;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::LazyOption;
+use near_sdk::collections::{LazyOption, LookupMap, LookupSet, UnorderedSet, Vector};
 use near_sdk::json_types::U128;
 use near_sdk::{
     assert_one_yocto, env, ext_contract, log, near_bindgen, require, AccountId, Balance,
-    BorshStorageKey, PanicOnDefault, Promise, PromiseOrValue,
+    BorshStorageKey, CryptoHash, PanicOnDefault, Promise, PromiseOrValue, Timestamp,
 };
 use rust_decimal::prelude::*;
 
+pub use crate::buyback::*;
+pub use crate::checkpoints::*;
+pub use crate::circuit_breaker::*;
+pub use crate::dividends::*;
+pub(crate) use crate::events::*;
+pub use crate::intents::*;
+pub use crate::market::*;
+pub use crate::oracle::*;
+pub use crate::polls::*;
+pub use crate::referrals::*;
+pub use crate::royalty::*;
+pub use crate::staking::*;
+pub use crate::vesting::*;
+pub use crate::winddown::*;
+
+mod allowance;
+mod buyback;
+mod checkpoints;
+mod circuit_breaker;
+mod dividends;
+mod events;
+mod intents;
+mod market;
+mod metadata;
+mod oracle;
+mod polls;
+mod referrals;
+mod royalty;
+mod staking;
+mod trade;
+mod upgrade;
+mod vesting;
+mod winddown;
+
+#[allow(clippy::assign_op_pattern, clippy::manual_div_ceil)]
+mod u256 {
+    uint::construct_uint! {
+        /// 256-bit unsigned integer for intermediate products that overflow u128.
+        pub struct U256(4);
+    }
+}
+pub use u256::U256;
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
+    owner_id: AccountId,
+    /// Account that deployed and initialized the coin, the only one allowed to update its code.
+    factory_id: AccountId,
+    approved_code_hash: Option<CryptoHash>,
+    reserve_token_id: AccountId,
+    curve: CurveParams,
     token: FungibleToken,
     lzr_locked: u128,
     metadata: LazyOption<FungibleTokenMetadata>,
+    vesting_schedules: LookupMap<AccountId, VestingSchedule>,
+    dividend_per_share: u128,
+    dividend_accounts: LookupMap<AccountId, DividendAccount>,
+    staking: StakingPool,
+    stakes: LookupMap<AccountId, StakeAccount>,
+    account_checkpoints: LookupMap<AccountId, CheckpointHistory>,
+    supply_checkpoints: CheckpointHistory,
+    snapshots: Vector<Snapshot>,
+    polls: Vector<Poll>,
+    poll_votes: LookupMap<(u64, AccountId), u32>,
+    /// LZR left over from swaps or earned from referrals, withdrawable by its owner.
+    lzr_credits: LookupMap<AccountId, Balance>,
+    referral_config: ReferralConfig,
+    referrals: LookupMap<AccountId, ReferralAccount>,
+    /// Royalty in basis points taken from transfers between fans.
+    transfer_royalty_bps: u16,
+    royalty_exemptions: UnorderedSet<AccountId>,
+    unclaimed_royalties: Balance,
+    buyback_lzr_spent: Balance,
+    buyback_coins_burned: Balance,
+    wind_down: Option<WindDown>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Spot price the current circuit breaker window started at, 0 before its first trade.
+    reference_price: Balance,
+    reference_price_at: Timestamp,
+    trading_halted_until: Timestamp,
+    /// Cumulative spot price times seconds, see the `oracle` module.
+    price_cumulative: u128,
+    /// Second `price_cumulative` was last updated at.
+    price_cumulative_at: u64,
+    observations: Vector<Observation>,
+    next_observation: u64,
+    market: MarketCounters,
+    holders: LookupSet<AccountId>,
+    allowances: Allowances,
+    intent_keys: IntentKeys,
 }
 
-const RESERVE_RATIO: f64 = 0.3333333333333333;
-const SLOPE: f64 = 0.003;
 const TOKEN_DECIMAL: u32 = 24;
 const BASE: u128 = 10;
+/// Share of every buy kept out of the reserve, including the founder reward.
+const BUY_FEE_PERCENT: u128 = 10;
 
+/// Reserve token used when a coin is initialized without explicit args.
 fn get_lzr_token_contract() -> AccountId {
     "lzr.testnet".parse().unwrap()
 }
@@ -31,12 +125,34 @@ fn get_lzr_token_contract() -> AccountId {
 #[ext_contract(ext_ft_transfer)]
 pub trait LoozrFt {
     fn ft_transfer(receiver_id: AccountId, amount: U128);
+    fn ft_transfer_call(
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128>;
 }
 
 #[derive(BorshSerialize, BorshStorageKey)]
 enum StorageKey {
     FungibleToken,
     Metadata,
+    VestingSchedules,
+    DividendAccounts,
+    Stakes,
+    AccountCheckpoints,
+    Checkpoints { account_hash: CryptoHash },
+    SupplyCheckpoints,
+    Snapshots,
+    Polls,
+    PollVotes,
+    LzrCredits,
+    Referrals,
+    RoyaltyExemptions,
+    Observations,
+    Holders,
+    Allowances,
+    IntentKeys,
 }
 
 #[near_bindgen]
@@ -59,17 +175,82 @@ impl Contract {
 
     #[init]
     pub fn new(owner_id: AccountId, metadata: FungibleTokenMetadata) -> Self {
+        Self::new_from_args(CreatorCoinArgs {
+            creator_id: owner_id,
+            reserve_token_id: get_lzr_token_contract(),
+            metadata,
+            curve: CurveParams::default(),
+        })
+    }
+
+    /// Init method the factory calls, see [`creator_coin_common::CREATOR_COIN_INIT_METHOD`].
+    #[init]
+    pub fn new_from_args(args: CreatorCoinArgs) -> Self {
         require!(!env::state_exists(), "Already initialized");
+        let CreatorCoinArgs {
+            creator_id: owner_id,
+            reserve_token_id,
+            metadata,
+            curve,
+        } = args;
         metadata.assert_valid();
+        require!(
+            metadata.decimals == TOKEN_DECIMAL as u8,
+            "Creator coins must use 24 decimals"
+        );
+        curve.assert_valid();
         let mut this = Self {
+            owner_id: owner_id.clone(),
+            factory_id: env::predecessor_account_id(),
+            approved_code_hash: None,
+            reserve_token_id,
+            curve,
             lzr_locked: 0,
             token: FungibleToken::new(StorageKey::FungibleToken),
             metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
+            vesting_schedules: LookupMap::new(StorageKey::VestingSchedules),
+            dividend_per_share: 0,
+            dividend_accounts: LookupMap::new(StorageKey::DividendAccounts),
+            staking: StakingPool::default(),
+            stakes: LookupMap::new(StorageKey::Stakes),
+            account_checkpoints: LookupMap::new(StorageKey::AccountCheckpoints),
+            supply_checkpoints: CheckpointHistory::new(StorageKey::SupplyCheckpoints),
+            snapshots: Vector::new(StorageKey::Snapshots),
+            polls: Vector::new(StorageKey::Polls),
+            poll_votes: LookupMap::new(StorageKey::PollVotes),
+            lzr_credits: LookupMap::new(StorageKey::LzrCredits),
+            referral_config: ReferralConfig::default(),
+            referrals: LookupMap::new(StorageKey::Referrals),
+            transfer_royalty_bps: 0,
+            royalty_exemptions: UnorderedSet::new(StorageKey::RoyaltyExemptions),
+            unclaimed_royalties: 0,
+            buyback_lzr_spent: 0,
+            buyback_coins_burned: 0,
+            wind_down: None,
+            circuit_breaker: None,
+            reference_price: 0,
+            reference_price_at: 0,
+            trading_halted_until: 0,
+            price_cumulative: 0,
+            price_cumulative_at: 0,
+            observations: Vector::new(StorageKey::Observations),
+            next_observation: 0,
+            market: MarketCounters::default(),
+            holders: LookupSet::new(StorageKey::Holders),
+            allowances: Allowances::new(StorageKey::Allowances),
+            intent_keys: IntentKeys::new(StorageKey::IntentKeys),
         };
+        this.measure_checkpoint_storage_usage();
+        this.measure_dividend_storage_usage();
+        this.measure_holder_storage_usage();
         this.token.internal_register_account(&owner_id);
         this
     }
 
+    pub fn reserve_token(&self) -> AccountId {
+        self.reserve_token_id.clone()
+    }
+
     pub fn reserve_balance(self) -> U128 {
         self.lzr_locked.into()
     }
@@ -83,18 +264,24 @@ impl Contract {
         account_id: AccountId,
         founder_id: AccountId,
         founder_percent: U128,
+        referrer_id: Option<AccountId>,
     ) -> Promise {
         assert_one_yocto();
+        self.assert_not_wound_down();
 
         let amount: Balance = amount.into();
         let founder_reward_percent: Balance = founder_percent.into();
         let founder_reward_amount = (amount * founder_reward_percent) / 100;
-        let deposit_amount = amount - (amount * 10) / 100;
+        let deposit_amount = amount - (amount * BUY_FEE_PERCENT) / 100;
 
         require!(deposit_amount > 0, "Must send loozr to buy tokens");
-        let tokens_minted = self.continous_mint(deposit_amount, account_id);
+        let tokens_minted = self.continous_mint(deposit_amount, account_id.clone());
+        if let Some(referrer_id) = referrer_id {
+            let protocol_fee = (amount - deposit_amount).saturating_sub(founder_reward_amount);
+            self.internal_reward_referrer(&referrer_id, &account_id, amount, protocol_fee);
+        }
 
-        ext_ft_transfer::ext(get_lzr_token_contract())
+        ext_ft_transfer::ext(self.reserve_token_id.clone())
             .with_attached_deposit(1)
             .ft_transfer(founder_id, founder_reward_amount.into())
             .then(Self::ext(env::current_account_id()).on_transfer_callback(tokens_minted))
@@ -103,38 +290,11 @@ impl Contract {
     #[private]
     #[payable]
     pub fn ft_burn(&mut self, sell_amount: U128, account_id: AccountId) -> Promise {
-        let user_account_id: AccountId = account_id.clone();
-        let cl_user_account_id: AccountId = user_account_id.clone();
+        let cl_user_account_id: AccountId = account_id.clone();
         assert_one_yocto();
         let sell_amount: Balance = sell_amount.into();
-        require!(sell_amount > 0, "Amount must be non-zero.");
-
-        let balance = self.internal_unwrap_balance_of(user_account_id);
-        require!(
-            balance >= sell_amount,
-            "Amount exceeds creator coin locked in"
-        );
-        require!(
-            self.lzr_locked > 0
-                && self.token.total_supply > 0
-                && sell_amount <= self.token.total_supply,
-            "Amount exceeds creator coin in supply"
-        );
-
-        let amount_in_near = Decimal::from_i128_with_scale(sell_amount as i128, TOKEN_DECIMAL);
-        let lzr_locked_in_near =
-            Decimal::from_i128_with_scale(self.lzr_locked as i128, TOKEN_DECIMAL);
-        let current_supply_in_near =
-            Decimal::from_i128_with_scale(self.token.total_supply as i128, TOKEN_DECIMAL);
-
-        let reimburse_amount = self.continous_sale(
-            current_supply_in_near,
-            lzr_locked_in_near,
-            amount_in_near,
-            sell_amount,
-            account_id,
-        );
-        ext_ft_transfer::ext(get_lzr_token_contract())
+        let reimburse_amount = self.internal_sell(account_id, sell_amount);
+        ext_ft_transfer::ext(self.reserve_token_id.clone())
             .with_attached_deposit(1)
             .ft_transfer(cl_user_account_id, reimburse_amount.into())
             .then(
@@ -148,14 +308,16 @@ impl Contract {
     }
 
     #[private]
-    pub fn on_transfer_callback(&mut self,
-        #[callback_result] call_result: Result<(), near_sdk::PromiseError>, tokens_minted: U128) -> PromiseOrValue<U128> {
-
-          if call_result.is_err() {
-             env::panic_str("Reserve balance overflow")
-          }else {
+    pub fn on_transfer_callback(
+        &mut self,
+        #[callback_result] call_result: Result<(), near_sdk::PromiseError>,
+        tokens_minted: U128,
+    ) -> PromiseOrValue<U128> {
+        if call_result.is_err() {
+            env::panic_str("Reserve balance overflow")
+        } else {
             PromiseOrValue::Value(tokens_minted)
-          }
+        }
     }
 
     #[private]
@@ -178,8 +340,8 @@ impl Contract {
 
             Promise::new(predecessor_account_id).transfer(attached_deposit.0);
             PromiseOrValue::Value(0.into())
-        }else {
-          PromiseOrValue::Value(reimburse_amount)
+        } else {
+            PromiseOrValue::Value(reimburse_amount)
         }
     }
 
@@ -191,10 +353,11 @@ impl Contract {
         sell_amount: u128,
         account_id: AccountId,
     ) -> u128 {
+        self.internal_before_trade();
         let reimburse_amount = self.calc_sales_return(
             current_supply_in_near,
             lzr_locked_in_near,
-            RESERVE_RATIO,
+            self.curve.reserve_ratio,
             amount_in_near,
         );
 
@@ -203,11 +366,14 @@ impl Contract {
             .checked_sub(reimburse_amount)
             .unwrap_or_else(|| env::panic_str("Reserve balance overflow"));
         self.internal_burn(sell_amount, account_id);
+        self.internal_after_trade();
+        self.internal_record_trade(reimburse_amount, false);
 
         reimburse_amount
     }
 
     fn continous_mint(&mut self, _deposit: u128, account_id: AccountId) -> U128 {
+        self.internal_before_trade();
         let amount = self.calc_purchase_return(_deposit);
 
         self.lzr_locked = self
@@ -215,6 +381,8 @@ impl Contract {
             .checked_add(_deposit)
             .unwrap_or_else(|| env::panic_str("Reserve balance overflow"));
         self.internal_mint(amount, account_id);
+        self.internal_after_trade();
+        self.internal_record_trade(_deposit, true);
         amount.into()
     }
 
@@ -258,8 +426,8 @@ impl Contract {
             return self.calc_mint_polynomial(
                 deposit_in_near,
                 total_supply_in_near,
-                RESERVE_RATIO,
-                Decimal::from_f64(SLOPE).unwrap(),
+                self.curve.reserve_ratio,
+                Decimal::from_f64(self.curve.slope).unwrap(),
             );
         }
 
@@ -305,15 +473,17 @@ impl Contract {
         // p = loozr_amount
         // rb = lzr_locked
         // x = current_supply
-        // r = RESERVE_RATIO
+        // r = curve.reserve_ratio
 
         let lzr_locked_in_near =
             Decimal::from_i128_with_scale(self.lzr_locked as i128, TOKEN_DECIMAL);
 
         let mut result = amount / lzr_locked_in_near;
         result = current_supply
-            * Decimal::from_f64((1. + self.decimal_to_float(result)).powf(RESERVE_RATIO) - 1.)
-                .unwrap();
+            * Decimal::from_f64(
+                (1. + self.decimal_to_float(result)).powf(self.curve.reserve_ratio) - 1.,
+            )
+            .unwrap();
 
         return (self.decimal_to_float(result) * BASE.pow(TOKEN_DECIMAL) as f64) as u128;
     }
@@ -327,6 +497,7 @@ impl Contract {
             .total_supply
             .checked_add(amount)
             .unwrap_or_else(|| env::panic_str("Total supply overflow"));
+        self.internal_checkpoint_supply();
     }
 
     fn internal_burn(&mut self, amount: Balance, account_id: AccountId) {
@@ -344,6 +515,7 @@ impl Contract {
             .total_supply
             .checked_sub(amount)
             .unwrap_or_else(|| env::panic_str("Total supply overflow"));
+        self.internal_checkpoint_supply();
     }
 
     fn decimal_to_float(&self, amount: Decimal) -> f64 {
@@ -359,11 +531,13 @@ impl Contract {
     /// Inner method to save the given account for a given account ID.
     /// If the account balance is 0, the account is deleted instead to release storage.
     fn internal_update_account(&mut self, account_id: &AccountId, balance: u128) {
+        self.internal_before_balance_change(account_id);
         if balance == 0 {
             self.token.accounts.remove(account_id);
         } else {
             self.token.accounts.insert(account_id, &balance);
         }
+        self.internal_after_balance_change(account_id);
     }
 
     fn internal_unwrap_balance_of(&self, account_id: AccountId) -> Balance {
@@ -373,7 +547,56 @@ impl Contract {
         }
     }
 
+    /// Settles every per-account accumulator before the balance of `account_id` changes.
+    /// Anything that moves coins must call this for each account it touches.
+    fn internal_before_balance_change(&mut self, account_id: &AccountId) {
+        self.internal_settle_dividends(account_id);
+    }
+
+    /// Records the new balance of `account_id` once it has changed.
+    fn internal_after_balance_change(&mut self, account_id: &AccountId) {
+        self.internal_checkpoint_account(account_id);
+        self.internal_track_holder(account_id);
+    }
+
+    /// Transfers `amount` from `sender_id` to `receiver_id` like `ft_transfer` would if
+    /// `sender_id` had called it, for transfers made on the sender's behalf.
+    pub(crate) fn internal_transfer_from(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: Balance,
+        memo: Option<String>,
+    ) {
+        self.assert_not_wound_down();
+        self.internal_assert_unlocked(sender_id, amount);
+        self.internal_before_balance_change(sender_id);
+        self.internal_before_balance_change(receiver_id);
+        let royalty = self.internal_take_royalty(sender_id, receiver_id, amount);
+        self.token
+            .internal_transfer(sender_id, receiver_id, amount - royalty, memo);
+        self.internal_after_balance_change(sender_id);
+        self.internal_after_balance_change(receiver_id);
+    }
+
+    /// Coins of `account_id` that cannot be sold or transferred right now.
+    fn internal_locked_balance(&self, account_id: &AccountId) -> Balance {
+        self.internal_staked_balance(account_id)
+    }
+
+    fn internal_assert_unlocked(&self, account_id: &AccountId, amount: Balance) {
+        let balance = self.internal_unwrap_balance_of(account_id.clone());
+        require!(
+            balance.saturating_sub(self.internal_locked_balance(account_id)) >= amount,
+            "Amount exceeds unlocked balance"
+        );
+    }
+
     fn on_account_closed(&mut self, account_id: AccountId, balance: Balance) {
+        self.internal_track_holder(&account_id);
+        self.internal_remove_checkpoints(&account_id);
+        self.internal_remove_dividend_account(&account_id);
+        self.internal_remove_stake(&account_id);
         log!("Closed @{} with {}", account_id, balance);
     }
 
@@ -382,9 +605,131 @@ impl Contract {
     }
 }
 
-near_contract_standards::impl_fungible_token_core!(Contract, token, on_tokens_burned);
+#[near_bindgen]
+impl FungibleTokenCore for Contract {
+    #[payable]
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+        self.assert_not_wound_down();
+        let sender_id = env::predecessor_account_id();
+        self.internal_assert_unlocked(&sender_id, amount.into());
+        self.internal_before_balance_change(&sender_id);
+        self.internal_before_balance_change(&receiver_id);
+        let royalty = self.internal_take_royalty(&sender_id, &receiver_id, amount.into());
+        self.token
+            .ft_transfer(receiver_id.clone(), (amount.0 - royalty).into(), memo);
+        self.internal_after_balance_change(&sender_id);
+        self.internal_after_balance_change(&receiver_id);
+    }
+
+    #[payable]
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.assert_not_wound_down();
+        let sender_id = env::predecessor_account_id();
+        self.internal_assert_unlocked(&sender_id, amount.into());
+        self.internal_before_balance_change(&sender_id);
+        self.internal_before_balance_change(&receiver_id);
+        let royalty = self.internal_take_royalty(&sender_id, &receiver_id, amount.into());
+        let result = self.token.ft_transfer_call(
+            receiver_id.clone(),
+            (amount.0 - royalty).into(),
+            memo,
+            msg,
+        );
+        self.internal_after_balance_change(&sender_id);
+        self.internal_after_balance_change(&receiver_id);
+        result
+    }
+
+    fn ft_total_supply(&self) -> U128 {
+        self.token.ft_total_supply()
+    }
+
+    fn ft_balance_of(&self, account_id: AccountId) -> U128 {
+        self.token.ft_balance_of(account_id)
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenResolver for Contract {
+    #[private]
+    fn ft_resolve_transfer(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    ) -> U128 {
+        self.internal_before_balance_change(&sender_id);
+        self.internal_before_balance_change(&receiver_id);
+        let (used_amount, burned_amount) =
+            self.token
+                .internal_ft_resolve_transfer(&sender_id, receiver_id.clone(), amount);
+        self.internal_after_balance_change(&sender_id);
+        self.internal_after_balance_change(&receiver_id);
+        if burned_amount > 0 {
+            self.internal_checkpoint_supply();
+            self.on_tokens_burned(sender_id, burned_amount);
+        }
+        used_amount.into()
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenReceiver for Contract {
+    /// Accepts LZR sent with `ft_transfer_call`. The `msg` selects what the LZR is for:
+    /// - `"distribute"` shares it pro-rata between all holders as dividends.
+    /// - `"stake_rewards"` adds it to the staking reward pool.
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        require!(
+            env::predecessor_account_id() == self.reserve_token_id,
+            "Only the reserve token is accepted"
+        );
+        match msg.as_str() {
+            "distribute" => {
+                self.internal_distribute_dividends(&sender_id, amount.into());
+                PromiseOrValue::Value(0.into())
+            }
+            "stake_rewards" => {
+                self.internal_fund_staking_rewards_lzr(amount.into());
+                PromiseOrValue::Value(0.into())
+            }
+            "buyback_and_burn" => {
+                self.internal_buyback_and_burn(&sender_id, amount.into());
+                PromiseOrValue::Value(0.into())
+            }
+            _ => {
+                let buy: BuyMessage = near_sdk::serde_json::from_str(&msg)
+                    .unwrap_or_else(|_| env::panic_str("Unsupported message"));
+                self.internal_buy(
+                    buy.receiver_id.unwrap_or(sender_id),
+                    amount.into(),
+                    buy.min_out.map(Balance::from).unwrap_or(0),
+                    buy.referrer_id,
+                );
+                PromiseOrValue::Value(0.into())
+            }
+        }
+    }
+}
+
 near_contract_standards::impl_fungible_token_storage!(Contract, token, on_account_closed);
 
+impl CreatorCoinInit for Contract {
+    fn new_from_args(args: CreatorCoinArgs) -> Self {
+        Contract::new_from_args(args)
+    }
+}
+
 #[near_bindgen]
 impl FungibleTokenMetadataProvider for Contract {
     fn ft_metadata(&self) -> FungibleTokenMetadata {
@@ -394,6 +739,7 @@ impl FungibleTokenMetadataProvider for Contract {
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
+    use near_contract_standards::storage_management::StorageManagement;
     use near_sdk::test_utils::{accounts, VMContextBuilder};
     use near_sdk::testing_env;
 
@@ -408,6 +754,25 @@ mod tests {
         builder
     }
 
+    // A coin owned by accounts(1), with the context caught up to its init.
+    fn setup_contract() -> (VMContextBuilder, Contract) {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let contract = Contract::new_default_meta(accounts(1), "".to_string());
+        testing_env!(context.storage_usage(env::storage_usage()).build());
+        (context, contract)
+    }
+
+    // Registers `account_id` with the minimum storage deposit, leaving it as the predecessor.
+    fn register(context: &mut VMContextBuilder, contract: &mut Contract, account_id: AccountId) {
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(contract.storage_balance_bounds().min.into())
+            .predecessor_account_id(account_id)
+            .build());
+        contract.storage_deposit(None, None);
+    }
+
     #[test]
     fn test_new() {
         let mut context = get_context(accounts(1));
@@ -427,6 +792,7 @@ mod tests {
             accounts(1),
             accounts(2),
             10.into(),
+            None,
         );
         let balance = contract.ft_total_supply();
         let creator_token_minted: u128 = 20800838230519037072244736;
@@ -453,6 +819,40 @@ mod tests {
         _contract.continous_mint(10, accounts(1));
     }
 
+    // Arguments of `#[init] new_from_args`, decoded the way its generated wasm entry point does.
+    #[derive(near_sdk::serde::Deserialize)]
+    #[serde(crate = "near_sdk::serde")]
+    struct NewFromArgsInput {
+        args: CreatorCoinArgs,
+    }
+
+    #[test]
+    fn test_new_from_init_payload() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let args = CreatorCoinArgs {
+            creator_id: accounts(1),
+            reserve_token_id: get_lzr_token_contract(),
+            metadata: FungibleTokenMetadata {
+                spec: FT_METADATA_SPEC.to_string(),
+                name: "Burna Boy".to_string(),
+                symbol: "BURNA".to_string(),
+                icon: None,
+                reference: None,
+                reference_hash: None,
+                decimals: TOKEN_DECIMAL as u8,
+            },
+            curve: CurveParams::default(),
+        };
+
+        let input: NewFromArgsInput =
+            near_sdk::serde_json::from_slice(&args.to_init_payload()).unwrap();
+        let contract = Contract::new_from_args(input.args);
+        if contract.owner_id != accounts(1) || contract.ft_metadata().symbol != "BURNA" {
+            env::panic_str("INIT PAYLOAD NOT ACCEPTED BY new_from_args");
+        }
+    }
+
     #[test]
     fn test_transfer() {
         let mut context = get_context(accounts(2));
@@ -487,4 +887,866 @@ mod tests {
             env::panic_str("BALANCE DOES NOT MATCH TRANSFER AMOUNT");
         }
     }
+
+    #[test]
+    fn test_vesting() {
+        let (mut context, mut contract) = setup_contract();
+        contract.continous_mint(500000000000000000000000000, accounts(1));
+        let owner_balance = contract.ft_balance_of(accounts(1)).0;
+
+        let total: Balance = 1000000000000000000000000;
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(10000000000000000000000)
+            .block_timestamp(1000)
+            .build());
+        contract.create_vesting_schedule(
+            accounts(2),
+            total.into(),
+            1000.into(),
+            100.into(),
+            1000.into(),
+        );
+        if contract.ft_balance_of(accounts(1)).0 != owner_balance - total {
+            env::panic_str("VESTED AMOUNT NOT MOVED TO ESCROW");
+        }
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(0)
+            .predecessor_account_id(accounts(2))
+            .block_timestamp(1500)
+            .build());
+        let vesting = contract.vesting_balance_of(accounts(2));
+        if vesting.vested.0 != total / 2 || vesting.unvested.0 != total / 2 {
+            env::panic_str("INCORRECT LINEAR VESTING");
+        }
+        contract.claim_vested();
+        if contract.ft_balance_of(accounts(2)).0 != total / 2 {
+            env::panic_str("CLAIMED AMOUNT DOES NOT MATCH VESTED");
+        }
+        if contract.ft_balance_of(accounts(0)).0 != total / 2 {
+            env::panic_str("ESCROW DOES NOT HOLD UNVESTED AMOUNT");
+        }
+    }
+
+    #[test]
+    fn test_dividends() {
+        let (mut context, mut contract) = setup_contract();
+        contract.continous_mint(500000000000000000000000000, accounts(1));
+        let supply = contract.ft_total_supply().0;
+        register(&mut context, &mut contract, accounts(2));
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(get_lzr_token_contract())
+            .build());
+        let revenue: Balance = 3000000000000000000000000;
+        contract.ft_on_transfer(accounts(3), revenue.into(), "distribute".to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(1))
+            .build());
+        contract.ft_transfer(accounts(2), (supply / 2).into(), None);
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(get_lzr_token_contract())
+            .build());
+        contract.ft_on_transfer(accounts(3), revenue.into(), "distribute".to_string());
+
+        // the first distribution all went to accounts(1), the second was split in half
+        let first_holder = contract.dividends_of(accounts(1)).0;
+        let second_holder = contract.dividends_of(accounts(2)).0;
+        if (revenue + revenue / 2).abs_diff(first_holder) > 1000 {
+            env::panic_str("INCORRECT DIVIDENDS FOR FIRST HOLDER");
+        }
+        if (revenue / 2).abs_diff(second_holder) > 1000 {
+            env::panic_str("INCORRECT DIVIDENDS FOR SECOND HOLDER");
+        }
+    }
+
+    #[test]
+    fn test_staking() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.block_index(10).build());
+        let mut contract = Contract::new_default_meta(accounts(1), "".to_string());
+        contract.continous_mint(500000000000000000000000000, accounts(1));
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+        let reward_per_block: Balance = 1000000000000000000000;
+        contract.configure_staking(
+            StakingRewardToken::CreatorCoin,
+            reward_per_block.into(),
+            1000.into(),
+        );
+        contract.fund_staking_rewards((reward_per_block * 100).into());
+
+        let staked = contract.ft_balance_of(accounts(2)).0 / 2;
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(10000000000000000000000)
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.stake(staked.into());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(0)
+            .block_index(20)
+            .block_timestamp(5000000000)
+            .build());
+        let stake = contract.stake_of(accounts(2));
+        let rewards = stake.rewards.0;
+        if (reward_per_block * 10).abs_diff(rewards) > 1000 {
+            env::panic_str("INCORRECT STAKING REWARDS");
+        }
+        if stake.stake_seconds.0 != staked * 5 {
+            env::panic_str("INCORRECT STAKE SECONDS");
+        }
+        if contract.internal_locked_balance(&accounts(2)) != staked {
+            env::panic_str("STAKED BALANCE IS NOT LOCKED");
+        }
+
+        contract.unstake(staked.into());
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .block_timestamp(5000001000)
+            .build());
+        contract.withdraw_unstaked();
+        contract.claim_staking_rewards();
+        if contract.internal_locked_balance(&accounts(2)) != 0 {
+            env::panic_str("UNSTAKED BALANCE IS STILL LOCKED");
+        }
+        if contract.ft_balance_of(accounts(2)).0 != staked * 2 + rewards {
+            env::panic_str("STAKING REWARDS NOT PAID OUT");
+        }
+    }
+
+    #[test]
+    fn test_staking_account_closed() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.block_index(10).build());
+        let mut contract = Contract::new_default_meta(accounts(1), "".to_string());
+        contract.continous_mint(500000000000000000000000000, accounts(1));
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+        let reward_per_block: Balance = 1000000000000000000000;
+        contract.configure_staking(
+            StakingRewardToken::CreatorCoin,
+            reward_per_block.into(),
+            1000.into(),
+        );
+        contract.fund_staking_rewards((reward_per_block * 100).into());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(10000000000000000000000)
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.stake(contract.ft_balance_of(accounts(2)));
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .block_index(20)
+            .build());
+        contract.storage_unregister(Some(true));
+        let pool = contract.staking_pool();
+        if pool.total_staked.0 != 0 {
+            env::panic_str("CLOSED STAKE STILL COUNTED");
+        }
+        if (reward_per_block * 100).abs_diff(pool.reward_pool.0) > 1000 {
+            env::panic_str("UNCLAIMED REWARDS NOT RETURNED TO POOL");
+        }
+        if contract.stake_of(accounts(2)).staked.0 != 0 {
+            env::panic_str("STAKE NOT REMOVED");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Unbonding period must be at most")]
+    fn test_staking_unbonding_period_cap() {
+        let (_, mut contract) = setup_contract();
+        contract.configure_staking(
+            StakingRewardToken::CreatorCoin,
+            0.into(),
+            (MIN_REDEMPTION_PERIOD / 2 + 1).into(),
+        );
+    }
+
+    #[test]
+    fn test_checkpoints() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.block_index(5).build());
+        let mut contract = Contract::new_default_meta(accounts(1), "".to_string());
+        contract.continous_mint(500000000000000000000000000, accounts(1));
+        let minted = contract.ft_balance_of(accounts(1)).0;
+        register(&mut context, &mut contract, accounts(2));
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(10000000000000000000000)
+            .predecessor_account_id(accounts(1))
+            .build());
+        contract.take_snapshot();
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .block_index(10)
+            .build());
+        let transfer_amount = minted / 4;
+        contract.ft_transfer(accounts(2), transfer_amount.into(), None);
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(10000000000000000000000)
+            .build());
+        contract.take_snapshot();
+
+        if contract.ft_balance_of_at(accounts(1), 5.into()).0 != minted
+            || contract.ft_balance_of_at(accounts(2), 5.into()).0 != 0
+        {
+            env::panic_str("INCORRECT BALANCE AT FIRST SNAPSHOT");
+        }
+        if contract.ft_balance_of_at(accounts(1), 10.into()).0 != minted - transfer_amount
+            || contract.ft_balance_of_at(accounts(2), 10.into()).0 != transfer_amount
+        {
+            env::panic_str("INCORRECT BALANCE AT SECOND SNAPSHOT");
+        }
+        if contract.ft_total_supply_at(5.into()).0 != minted {
+            env::panic_str("INCORRECT TOTAL SUPPLY AT SNAPSHOT");
+        }
+
+        // later activity, however frequent, does not push snapshots out of the history
+        for block in 11..11 + 2 * MAX_ACCOUNT_CHECKPOINTS {
+            testing_env!(context
+                .storage_usage(env::storage_usage())
+                .attached_deposit(1)
+                .block_index(block)
+                .build());
+            contract.ft_transfer(accounts(2), 1.into(), None);
+        }
+        if contract.ft_balance_of_at(accounts(1), 5.into()).0 != minted
+            || contract.ft_balance_of_at(accounts(2), 10.into()).0 != transfer_amount
+        {
+            env::panic_str("SNAPSHOT EVICTED BY TRANSFERS");
+        }
+
+        // every retained snapshot stays readable while newer ones retire the oldest
+        for block in 100..100 + MAX_SNAPSHOTS - 1 {
+            testing_env!(context
+                .storage_usage(env::storage_usage())
+                .attached_deposit(1)
+                .block_index(block)
+                .build());
+            contract.ft_transfer(accounts(2), 1.into(), None);
+            testing_env!(context
+                .storage_usage(env::storage_usage())
+                .attached_deposit(10000000000000000000000)
+                .build());
+            contract.take_snapshot();
+        }
+        let retained = contract.retained_snapshots();
+        if retained.len() as u64 != MAX_SNAPSHOTS || retained[0].0 != 10 {
+            env::panic_str("OLDEST SNAPSHOT NOT RETIRED");
+        }
+        if contract.ft_balance_of_at(accounts(1), 10.into()).0 != minted - transfer_amount
+            || contract.ft_balance_of_at(accounts(1), 100.into()).0
+                != contract.ft_balance_of_at(accounts(1), 101.into()).0 + 1
+        {
+            env::panic_str("INCORRECT BALANCE AT RETAINED SNAPSHOT");
+        }
+    }
+
+    #[test]
+    fn test_polls() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.block_index(5).build());
+        let mut contract = Contract::new_default_meta(accounts(1), "".to_string());
+        contract.continous_mint(500000000000000000000000000, accounts(1));
+        contract.continous_mint(100000000000000000000000000, accounts(2));
+        let first_weight = contract.ft_balance_of(accounts(1)).0;
+        let second_weight = contract.ft_balance_of(accounts(2)).0;
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(10000000000000000000000)
+            .block_index(6)
+            .build());
+        let poll_id = contract.create_poll(
+            "Next single?".to_string(),
+            vec!["Ballad".to_string(), "Banger".to_string()],
+            1000.into(),
+        );
+
+        // coins minted after the snapshot carry no weight
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .block_index(7)
+            .build());
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+        contract.vote(poll_id, 0);
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.vote(poll_id, 1);
+
+        let poll = contract.get_poll(poll_id).unwrap();
+        if poll.tallies[0].0 != first_weight || poll.tallies[1].0 != second_weight {
+            env::panic_str("INCORRECT POLL TALLIES");
+        }
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(0)
+            .block_timestamp(1000)
+            .build());
+        if contract.finalize_poll(poll_id) != 0 {
+            env::panic_str("INCORRECT POLL WINNER");
+        }
+    }
+
+    #[test]
+    fn test_update_metadata() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.attached_deposit(10000000000000000000000).build());
+        let mut contract = Contract::new_default_meta(accounts(1), "".to_string());
+        testing_env!(context.storage_usage(env::storage_usage()).build());
+
+        let reference_hash = env::sha256(b"{\"bio\":\"Singer from Lagos\"}");
+        contract.update_metadata(
+            Some("Burna Coin".to_string()),
+            None,
+            Some("https://loozr.io/profiles/burna.json".to_string()),
+            Some(reference_hash.clone().into()),
+            None,
+        );
+
+        let metadata = contract.ft_metadata();
+        if metadata.name != "Burna Coin" || metadata.symbol != "LZR-CT" {
+            env::panic_str("METADATA NOT UPDATED");
+        }
+        if metadata.reference_hash.unwrap().0 != reference_hash {
+            env::panic_str("REFERENCE HASH NOT STORED");
+        }
+        if metadata.icon != Some("".to_string()) {
+            env::panic_str("ICON SHOULD BE UNCHANGED");
+        }
+
+        let metadata = contract.update_metadata(None, Some("icon".to_string()), None, None, None);
+        if metadata.reference.is_none() || metadata.reference_hash.is_none() {
+            env::panic_str("REFERENCE WIPED BY ICON UPDATE");
+        }
+        let metadata = contract.update_metadata(None, None, None, None, Some(true));
+        if metadata.reference.is_some() || metadata.reference_hash.is_some() {
+            env::panic_str("REFERENCE NOT CLEARED");
+        }
+
+        // accounts(1) initialized the coin, so it also stands in for the factory
+        contract.set_symbol("BURNA".to_string());
+        if contract.ft_metadata().symbol != "BURNA" {
+            env::panic_str("SYMBOL NOT SET BY THE FACTORY");
+        }
+    }
+
+    #[test]
+    fn test_code_update() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1), "".to_string());
+        if contract.factory_id() != accounts(0) {
+            env::panic_str("FACTORY NOT RECORDED");
+        }
+
+        let code = b"new coin wasm".to_vec();
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.approve_code_update(env::sha256_array(&code).into());
+        if contract.approved_code_update().is_none() {
+            env::panic_str("CODE UPDATE NOT APPROVED");
+        }
+
+        let mut update_context = context.predecessor_account_id(accounts(0)).build();
+        update_context.input = code;
+        testing_env!(update_context);
+        contract.update_code();
+        if contract.approved_code_update().is_some() {
+            env::panic_str("APPROVAL NOT USED UP");
+        }
+    }
+
+    #[test]
+    fn test_buy_with_transfer() {
+        let (mut context, mut contract) = setup_contract();
+        register(&mut context, &mut contract, accounts(2));
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(0)
+            .predecessor_account_id(get_lzr_token_contract())
+            .build());
+        let amount: Balance = 1000000000000000000000000;
+        let msg = BuyMessage {
+            receiver_id: Some(accounts(2)),
+            min_out: Some(1.into()),
+            referrer_id: None,
+        };
+        contract.ft_on_transfer(accounts(3), amount.into(), msg.to_msg());
+        if contract.ft_balance_of(accounts(2)).0 == 0 {
+            env::panic_str("COINS NOT MINTED TO RECEIVER");
+        }
+        if contract.lzr_locked != amount - amount * BUY_FEE_PERCENT / 100 {
+            env::panic_str("INCORRECT RESERVE");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Receiver is not registered")]
+    fn test_buy_for_unregistered_receiver() {
+        let (mut context, mut contract) = setup_contract();
+        testing_env!(context
+            .attached_deposit(0)
+            .predecessor_account_id(get_lzr_token_contract())
+            .build());
+        let msg = BuyMessage {
+            receiver_id: Some(accounts(2)),
+            min_out: None,
+            referrer_id: None,
+        };
+        contract.ft_on_transfer(accounts(3), 1000000000000000000000000.into(), msg.to_msg());
+    }
+
+    #[test]
+    fn test_swap() {
+        let coin_a: AccountId = "burna.factory.testnet".parse().unwrap();
+        let coin_b: AccountId = "wizkid.factory.testnet".parse().unwrap();
+        let mut context = get_context("factory.testnet".parse().unwrap());
+        testing_env!(context.current_account_id(coin_a).build());
+        let mut contract = Contract::new_default_meta(accounts(1), "".to_string());
+        testing_env!(context.storage_usage(env::storage_usage()).build());
+        contract.continous_mint(500000000000000000000000000, accounts(1));
+        let lzr_locked = contract.lzr_locked;
+        let supply = contract.ft_total_supply().0;
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(1))
+            .build());
+        contract.swap((supply / 2).into(), coin_b.clone(), Some(1.into()));
+        if contract.ft_total_supply().0 != supply / 2 || contract.lzr_locked >= lzr_locked {
+            env::panic_str("COINS NOT SOLD");
+        }
+        let proceeds = lzr_locked - contract.lzr_locked;
+
+        // the target used only part of the proceeds, the rest becomes withdrawable credit
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(0)
+            .predecessor_account_id(env::current_account_id())
+            .build());
+        contract.on_swap_settled(
+            Ok((proceeds - 1000).into()),
+            accounts(1),
+            coin_b,
+            proceeds.into(),
+        );
+        if contract.lzr_credit_of(accounts(1)).0 != 1000 {
+            env::panic_str("UNUSED PROCEEDS NOT CREDITED");
+        }
+    }
+
+    #[test]
+    fn test_sell() {
+        let (mut context, mut contract) = setup_contract();
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+        let lzr_locked = contract.lzr_locked;
+        let balance = contract.ft_balance_of(accounts(2)).0;
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.sell((balance / 2).into(), Some(1.into()));
+        if contract.ft_balance_of(accounts(2)).0 != balance - balance / 2
+            || contract.lzr_locked >= lzr_locked
+        {
+            env::panic_str("COINS NOT SOLD");
+        }
+    }
+
+    #[test]
+    fn test_transfer_royalty() {
+        let (mut context, mut contract) = setup_contract();
+        for account_id in [accounts(2), accounts(3)] {
+            register(&mut context, &mut contract, account_id);
+        }
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(0)
+            .predecessor_account_id(accounts(1))
+            .build());
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+        contract.set_transfer_royalty(500);
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.ft_transfer(accounts(3), 1000.into(), None);
+        if contract.ft_balance_of(accounts(3)).0 != 950 {
+            env::panic_str("ROYALTY NOT DEDUCTED");
+        }
+        if contract.transfer_royalty().unclaimed.0 != 50 {
+            env::panic_str("ROYALTY NOT COLLECTED");
+        }
+
+        // transfers to the owner are exempt
+        contract.ft_transfer(accounts(1), 1000.into(), None);
+        if contract.ft_balance_of(accounts(1)).0 != 1000 {
+            env::panic_str("OWNER TRANSFER CHARGED");
+        }
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(0)
+            .predecessor_account_id(accounts(1))
+            .build());
+        contract.claim_royalties();
+        if contract.ft_balance_of(accounts(1)).0 != 1050 {
+            env::panic_str("ROYALTY NOT CLAIMED");
+        }
+    }
+
+    #[test]
+    fn test_referrals() {
+        let (mut context, mut contract) = setup_contract();
+        register(&mut context, &mut contract, accounts(2));
+        contract.storage_deposit(Some(accounts(4)), None);
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(0)
+            .predecessor_account_id(accounts(1))
+            .build());
+        let daily_cap: Balance = 40000000000000000000000;
+        contract.set_referral_config(ReferralConfig {
+            share_bps: 5000,
+            daily_cap: daily_cap.into(),
+        });
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(get_lzr_token_contract())
+            .build());
+        let amount: Balance = 1000000000000000000000000;
+        let msg = BuyMessage {
+            receiver_id: None,
+            min_out: None,
+            referrer_id: Some(accounts(4)),
+        };
+        contract.ft_on_transfer(accounts(2), amount.into(), msg.to_msg());
+        // half of the 5% protocol fee
+        if contract.referral_stats(accounts(4)).earned.0 != 25000000000000000000000 {
+            env::panic_str("INCORRECT REFERRAL REWARD");
+        }
+        contract.ft_on_transfer(accounts(2), amount.into(), msg.to_msg());
+        let stats = contract.referral_stats(accounts(4));
+        if stats.earned.0 != daily_cap || stats.claimable.0 != daily_cap {
+            env::panic_str("DAILY CAP NOT APPLIED");
+        }
+        if stats.referred_volume.0 != amount * 2 {
+            env::panic_str("INCORRECT REFERRED VOLUME");
+        }
+
+        // capped and unregistered referrers are not recorded
+        contract.ft_on_transfer(accounts(2), amount.into(), msg.to_msg());
+        let unregistered = BuyMessage {
+            referrer_id: Some(accounts(3)),
+            ..msg
+        };
+        contract.ft_on_transfer(accounts(2), amount.into(), unregistered.to_msg());
+        if contract.referral_stats(accounts(4)).referred_volume.0 != amount * 2
+            || contract.referrals.get(&accounts(3)).is_some()
+        {
+            env::panic_str("UNREWARDED REFERRAL RECORDED");
+        }
+    }
+
+    #[test]
+    fn test_buyback_and_burn() {
+        let (mut context, mut contract) = setup_contract();
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+        let supply = contract.ft_total_supply().0;
+        let lzr_locked = contract.lzr_locked;
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(get_lzr_token_contract())
+            .build());
+        let amount: Balance = 10000000000000000000000000;
+        contract.ft_on_transfer(accounts(1), amount.into(), "buyback_and_burn".to_string());
+        if contract.ft_total_supply().0 != supply {
+            env::panic_str("SUPPLY CHANGED");
+        }
+        if contract.lzr_locked != lzr_locked + amount {
+            env::panic_str("RESERVE NOT RAISED");
+        }
+        let stats = contract.buyback_stats();
+        if stats.lzr_spent.0 != amount || stats.coins_burned.0 == 0 {
+            env::panic_str("BUYBACK NOT RECORDED");
+        }
+    }
+
+    #[test]
+    fn test_wind_down() {
+        let (mut context, mut contract) = setup_contract();
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+        contract.continous_mint(300000000000000000000000000, accounts(3));
+
+        let redemption_period: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+        contract.wind_down(redemption_period.into(), None);
+        let wind_down = contract.wind_down_state().unwrap();
+        if wind_down.treasury_id != contract.factory_id || wind_down.swept {
+            env::panic_str("WRONG WIND DOWN STATE");
+        }
+
+        let supply = contract.ft_total_supply().0;
+        let lzr_locked = contract.lzr_locked;
+        let balance = contract.ft_balance_of(accounts(2)).0;
+        let expected =
+            (U256::from(balance) * U256::from(lzr_locked) / U256::from(supply)).as_u128();
+        if contract.redemption_value_of(accounts(2)).0 != expected {
+            env::panic_str("WRONG REDEMPTION VALUE");
+        }
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(accounts(2))
+            .attached_deposit(1)
+            .build());
+        contract.redeem();
+        if contract.ft_balance_of(accounts(2)).0 != 0
+            || contract.ft_total_supply().0 != supply - balance
+            || contract.lzr_locked != lzr_locked - expected
+        {
+            env::panic_str("REDEMPTION NOT APPLIED");
+        }
+        let remaining = contract.ft_balance_of(accounts(3)).0;
+        let before =
+            (U256::from(remaining) * U256::from(lzr_locked) / U256::from(supply)).as_u128();
+        if contract.redemption_value_of(accounts(3)).0.abs_diff(before) > 1 {
+            env::panic_str("REDEMPTION NOT PRO-RATA");
+        }
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .block_timestamp(redemption_period)
+            .attached_deposit(0)
+            .build());
+        contract.sweep_wind_down_dust();
+        if contract.lzr_locked != 0 || !contract.wind_down_state().unwrap().swept {
+            env::panic_str("RESERVE NOT SWEPT");
+        }
+    }
+
+    #[test]
+    fn test_circuit_breaker() {
+        let (_, mut contract) = setup_contract();
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+
+        let hour: u64 = 60 * 60 * 1_000_000_000;
+        contract.set_circuit_breaker(Some(CircuitBreakerConfig {
+            max_move_bps: 1000,
+            window: hour.into(),
+            cooldown: hour.into(),
+        }));
+        let price = contract.spot_price().0;
+        contract.continous_mint(1000000000000000000000000, accounts(2));
+        if contract.is_trading_halted() || contract.circuit_breaker().reference_price.0 != price {
+            env::panic_str("SMALL MOVE HALTED TRADING");
+        }
+        let balance = contract.ft_balance_of(accounts(2)).0;
+        let minted = contract.continous_mint(500000000000000000000000000, accounts(2));
+        if minted.0 == 0 || contract.ft_balance_of(accounts(2)).0 != balance + minted.0 {
+            env::panic_str("LARGE MOVE NOT SETTLED");
+        }
+        if !contract.is_trading_halted() || contract.circuit_breaker().halted_until.0 != hour {
+            env::panic_str("LARGE MOVE DID NOT HALT TRADING");
+        }
+
+        contract.resume_trading();
+        if contract.is_trading_halted() || contract.circuit_breaker().reference_price.0 != 0 {
+            env::panic_str("TRADING NOT RESUMED");
+        }
+    }
+
+    #[test]
+    fn test_price_oracle() {
+        let (mut context, mut contract) = setup_contract();
+        let second: u64 = 1_000_000_000;
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .block_timestamp(second)
+            .build());
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+        let first_price = contract.spot_price().0;
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .block_timestamp(11 * second)
+            .build());
+        contract.continous_mint(100000000000000000000000000, accounts(2));
+        let second_price = contract.spot_price().0;
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .block_timestamp(31 * second)
+            .build());
+        contract.continous_mint(100000000000000000000000000, accounts(2));
+
+        let observations = contract.observations();
+        if observations.len() != 3 || observations[1].price_cumulative.0 != first_price * 10 {
+            env::panic_str("WRONG OBSERVATIONS");
+        }
+        if contract.twap(11.into(), 31.into()).0 != second_price {
+            env::panic_str("WRONG TWAP");
+        }
+        if contract.twap(1.into(), 31.into()).0 != (first_price * 10 + second_price * 20) / 30 {
+            env::panic_str("WRONG LONG TWAP");
+        }
+        let cumulative = observations[2].price_cumulative.0 + contract.spot_price().0 * 10;
+        testing_env!(context.block_timestamp(41 * second).build());
+        if contract.price_cumulative().0 != cumulative {
+            env::panic_str("WRONG CUMULATIVE PRICE");
+        }
+    }
+
+    #[test]
+    fn test_market_stats() {
+        let (mut context, mut contract) = setup_contract();
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+        contract.continous_mint(300000000000000000000000000, accounts(3));
+        let ath_price = contract.spot_price().0;
+        let sold = contract.internal_sell(accounts(3), 1000000000000000000000000);
+
+        let stats = contract.market_stats();
+        let bought = 800000000000000000000000000;
+        if stats.volume_bought.0 != bought
+            || stats.volume_sold.0 != sold
+            || stats.trade_count.0 != 3
+            || stats.volume_24h.0 != bought + sold
+            || stats.hourly_volume[23].0 != bought + sold
+        {
+            env::panic_str("WRONG VOLUME");
+        }
+        if stats.holder_count.0 != 2 || stats.ath_price.0 != ath_price {
+            env::panic_str("WRONG HOLDERS OR ATH");
+        }
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(accounts(3))
+            .attached_deposit(1)
+            .build());
+        let balance = contract.ft_balance_of(accounts(3));
+        contract.ft_transfer(accounts(2), balance, None);
+        if contract.market_stats().holder_count.0 != 1 {
+            env::panic_str("HOLDER NOT REMOVED");
+        }
+        testing_env!(context
+            .block_timestamp(25 * 60 * 60 * 1_000_000_000)
+            .build());
+        if contract.market_stats().volume_24h.0 != 0 {
+            env::panic_str("OLD VOLUME NOT ROLLED OFF");
+        }
+    }
+
+    #[test]
+    fn test_allowance() {
+        let (mut context, mut contract) = setup_contract();
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+        register(&mut context, &mut contract, accounts(3));
+
+        let allowance: Balance = 20000000000000000000000000;
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(10_000_000_000_000_000_000_000)
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.ft_approve(accounts(4), allowance.into(), None);
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(4))
+            .build());
+        let transfer_amount = 15000000000000000000000000;
+        contract.ft_transfer_from(accounts(2), accounts(3), transfer_amount.into(), None);
+        if contract.ft_balance_of(accounts(3)).0 != transfer_amount {
+            env::panic_str("TRANSFER FROM NOT APPLIED");
+        }
+        if contract.ft_allowance(accounts(2), accounts(4)).0 != allowance - transfer_amount {
+            env::panic_str("ALLOWANCE NOT REDUCED");
+        }
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.ft_revoke(accounts(4));
+        if contract.ft_allowance(accounts(2), accounts(4)).0 != 0 {
+            env::panic_str("ALLOWANCE NOT REVOKED");
+        }
+    }
+
+    #[test]
+    fn test_intents() {
+        use ed25519_dalek::Signer;
+
+        let (mut context, mut contract) = setup_contract();
+        contract.continous_mint(500000000000000000000000000, accounts(2));
+
+        let secret = ed25519_dalek::SecretKey::from_bytes(&[7; 32]).unwrap();
+        let keypair = ed25519_dalek::Keypair {
+            public: (&secret).into(),
+            secret,
+        };
+        let mut public_key = vec![0];
+        public_key.extend_from_slice(keypair.public.as_bytes());
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(10_000_000_000_000_000_000_000)
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.register_intent_key(public_key.try_into().unwrap());
+
+        let sell_amount = contract.ft_balance_of(accounts(2)).0 / 2;
+        let supply = contract.ft_total_supply().0;
+        let intent = near_sdk::serde_json::to_string(&Intent {
+            signer_id: accounts(2),
+            contract_id: accounts(0),
+            nonce: 0.into(),
+            expires_at: 100.into(),
+            action: IntentAction::Sell {
+                amount: sell_amount.into(),
+            },
+        })
+        .unwrap();
+        let signature = keypair.sign(intent.as_bytes()).to_bytes().to_vec();
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(0)
+            .predecessor_account_id(accounts(4))
+            .build());
+        contract.execute_intent(intent, signature.into());
+
+        if contract.ft_total_supply().0 != supply - sell_amount {
+            env::panic_str("SELL INTENT NOT EXECUTED");
+        }
+        if contract.intent_key_of(accounts(2)).unwrap().next_nonce.0 != 1 {
+            env::panic_str("NONCE NOT SPENT");
+        }
+    }
 }
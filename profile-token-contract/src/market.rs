@@ -0,0 +1,138 @@
+//! Running market statistics, kept up to date by every trade and balance change so clients
+//! do not have to replay the coin's history.
+
+use crate::*;
+use near_sdk::json_types::U64;
+use near_sdk::serde::Serialize;
+
+const HOURS_PER_DAY: u64 = 24;
+const NANOSECONDS_PER_HOUR: u64 = 60 * 60 * 1_000_000_000;
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Default)]
+pub struct HourlyVolume {
+    /// Hours since the Unix epoch.
+    pub hour: u64,
+    pub volume: Balance,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct MarketCounters {
+    /// LZR added to the reserve by buys.
+    pub volume_bought: Balance,
+    /// LZR paid out of the reserve by sells.
+    pub volume_sold: Balance,
+    pub trade_count: u64,
+    /// Accounts other than the escrow holding a non-zero balance.
+    pub holder_count: u64,
+    pub ath_price: Balance,
+    pub ath_at: Timestamp,
+    /// Volume of the last 24 hours, indexed by hour of the day.
+    pub hourly_volume: [HourlyVolume; HOURS_PER_DAY as usize],
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MarketStats {
+    pub spot_price: U128,
+    /// Spot price times total supply, in yocto LZR.
+    pub market_cap: U128,
+    pub total_supply: U128,
+    pub reserve_balance: U128,
+    pub volume_bought: U128,
+    pub volume_sold: U128,
+    pub trade_count: U64,
+    pub holder_count: U64,
+    pub ath_price: U128,
+    pub ath_at: U64,
+    pub volume_24h: U128,
+    /// Volume of each of the last 24 hours, oldest first.
+    pub hourly_volume: Vec<U128>,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn market_stats(&self) -> MarketStats {
+        let spot_price = self.internal_spot_price();
+        let market_cap = U256::from(spot_price) * U256::from(self.token.total_supply)
+            / U256::from(BASE.pow(TOKEN_DECIMAL));
+        let current_hour = env::block_timestamp() / NANOSECONDS_PER_HOUR;
+        let hourly_volume: Vec<Balance> = (0..HOURS_PER_DAY)
+            .rev()
+            .map(|hours_ago| match current_hour.checked_sub(hours_ago) {
+                Some(hour) => {
+                    let bucket = self.market.hourly_volume[(hour % HOURS_PER_DAY) as usize];
+                    if bucket.hour == hour {
+                        bucket.volume
+                    } else {
+                        0
+                    }
+                }
+                None => 0,
+            })
+            .collect();
+
+        MarketStats {
+            spot_price: spot_price.into(),
+            market_cap: market_cap.as_u128().into(),
+            total_supply: self.token.total_supply.into(),
+            reserve_balance: self.lzr_locked.into(),
+            volume_bought: self.market.volume_bought.into(),
+            volume_sold: self.market.volume_sold.into(),
+            trade_count: self.market.trade_count.into(),
+            holder_count: self.market.holder_count.into(),
+            ath_price: self.market.ath_price.into(),
+            ath_at: self.market.ath_at.into(),
+            volume_24h: hourly_volume.iter().sum::<Balance>().into(),
+            hourly_volume: hourly_volume.into_iter().map(U128::from).collect(),
+        }
+    }
+}
+
+impl Contract {
+    /// Adds a trade of `lzr_amount` to the counters. Called after the trade settled.
+    pub(crate) fn internal_record_trade(&mut self, lzr_amount: Balance, is_buy: bool) {
+        if is_buy {
+            self.market.volume_bought += lzr_amount;
+        } else {
+            self.market.volume_sold += lzr_amount;
+        }
+        self.market.trade_count += 1;
+
+        let now = env::block_timestamp();
+        let hour = now / NANOSECONDS_PER_HOUR;
+        let bucket = &mut self.market.hourly_volume[(hour % HOURS_PER_DAY) as usize];
+        if bucket.hour != hour {
+            *bucket = HourlyVolume { hour, volume: 0 };
+        }
+        bucket.volume += lzr_amount;
+
+        let spot_price = self.internal_spot_price();
+        if spot_price > self.market.ath_price {
+            self.market.ath_price = spot_price;
+            self.market.ath_at = now;
+        }
+    }
+
+    /// Counts `account_id` as a holder while its balance is non-zero.
+    pub(crate) fn internal_track_holder(&mut self, account_id: &AccountId) {
+        if *account_id == env::current_account_id() {
+            return;
+        }
+        if self.token.accounts.get(account_id).unwrap_or(0) > 0 {
+            if self.holders.insert(account_id) {
+                self.market.holder_count += 1;
+            }
+        } else if self.holders.remove(account_id) {
+            self.market.holder_count -= 1;
+        }
+    }
+
+    /// Adds the storage of a holder record to what every account pays on registration.
+    pub(crate) fn measure_holder_storage_usage(&mut self) {
+        let initial_storage_usage = env::storage_usage();
+        let tmp_account_id = AccountId::new_unchecked("a".repeat(64));
+        self.holders.insert(&tmp_account_id);
+        self.token.account_storage_usage += env::storage_usage() - initial_storage_usage;
+        self.holders.remove(&tmp_account_id);
+    }
+}
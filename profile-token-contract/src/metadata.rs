@@ -0,0 +1,99 @@
+use crate::*;
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::serde::Serialize;
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct MetadataUpdateEvent<'a> {
+    owner_id: &'a AccountId,
+    metadata: &'a FungibleTokenMetadata,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Lets the creator rebrand their coin. Fields left as `None` keep their current value.
+    /// `reference` and `reference_hash` are replaced together, and `clear_reference` removes
+    /// both. `reference_hash` is the sha256 of the profile JSON `reference` points to.
+    /// The symbol is unique across the factory and changes through its `update_coin_symbol`.
+    /// Storage growth is paid from the attached deposit and freed storage is refunded.
+    #[payable]
+    pub fn update_metadata(
+        &mut self,
+        name: Option<String>,
+        icon: Option<String>,
+        reference: Option<String>,
+        reference_hash: Option<Base64VecU8>,
+        clear_reference: Option<bool>,
+    ) -> FungibleTokenMetadata {
+        self.assert_owner();
+        let mut metadata = self.metadata.get().unwrap();
+        if let Some(name) = name {
+            metadata.name = name;
+        }
+        if let Some(icon) = icon {
+            metadata.icon = Some(icon);
+        }
+        if clear_reference.unwrap_or(false) {
+            require!(
+                reference.is_none() && reference_hash.is_none(),
+                "Cannot set and clear the reference at once"
+            );
+            metadata.reference = None;
+            metadata.reference_hash = None;
+        } else if reference.is_some() || reference_hash.is_some() {
+            metadata.reference = reference;
+            metadata.reference_hash = reference_hash;
+        }
+        self.internal_set_metadata(&metadata, env::predecessor_account_id());
+        metadata
+    }
+
+    /// Changes the symbol to one the factory reserved for this coin, see
+    /// [`creator_coin_common::CREATOR_COIN_SYMBOL_METHOD`]. The deposit the owner attached
+    /// to the factory call pays for storage growth, the rest goes back to the owner.
+    #[payable]
+    pub fn set_symbol(&mut self, symbol: String) {
+        require!(
+            env::predecessor_account_id() == self.factory_id,
+            "Only the factory can change the symbol"
+        );
+        let mut metadata = self.metadata.get().unwrap();
+        metadata.symbol = symbol;
+        self.internal_set_metadata(&metadata, self.owner_id.clone());
+    }
+}
+
+impl Contract {
+    /// Validates and stores `metadata`. `payer_id` pays for storage growth out of the
+    /// attached deposit and gets back the rest and any freed storage.
+    fn internal_set_metadata(&mut self, metadata: &FungibleTokenMetadata, payer_id: AccountId) {
+        metadata.assert_valid();
+
+        let initial_storage_usage = env::storage_usage();
+        self.metadata.set(metadata);
+        let storage_usage = env::storage_usage();
+        let refund = if storage_usage >= initial_storage_usage {
+            let required_cost =
+                env::storage_byte_cost() * Balance::from(storage_usage - initial_storage_usage);
+            require!(
+                required_cost <= env::attached_deposit(),
+                format!("Must attach {} yoctoNEAR to cover storage", required_cost)
+            );
+            env::attached_deposit() - required_cost
+        } else {
+            env::storage_byte_cost() * Balance::from(initial_storage_usage - storage_usage)
+                + env::attached_deposit()
+        };
+        if refund > 1 {
+            Promise::new(payer_id).transfer(refund);
+        }
+
+        emit_event(
+            "ft_metadata_update",
+            MetadataUpdateEvent {
+                owner_id: &self.owner_id,
+                metadata,
+            },
+        );
+    }
+}
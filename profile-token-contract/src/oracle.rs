@@ -0,0 +1,96 @@
+//! Time-weighted average price oracle. Before every trade the spot price that held since the
+//! previous update is added to `price_cumulative`, weighted by the seconds it held, and the
+//! result is stored as an observation. The TWAP between two observations is the difference
+//! of their cumulative prices divided by the seconds between them. Cumulative prices wrap
+//! on overflow, which keeps their differences correct.
+
+use crate::*;
+use near_sdk::json_types::U64;
+use near_sdk::serde::Serialize;
+
+/// Observations kept in the ring buffer, older ones are overwritten.
+const MAX_OBSERVATIONS: u64 = 64;
+const NANOSECONDS_PER_SECOND: u64 = 1_000_000_000;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Observation {
+    /// Seconds since the Unix epoch.
+    pub timestamp: U64,
+    pub price_cumulative: U128,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Sum of the spot price, in yocto LZR per whole coin, times the seconds it held, up to now.
+    pub fn price_cumulative(&self) -> U128 {
+        let elapsed = now_seconds().saturating_sub(self.price_cumulative_at);
+        self.price_cumulative
+            .wrapping_add(self.internal_spot_price().wrapping_mul(u128::from(elapsed)))
+            .into()
+    }
+
+    /// Stored observations, oldest first.
+    pub fn observations(&self) -> Vec<Observation> {
+        let len = self.observations.len();
+        (0..len)
+            .map(|offset| {
+                let index = if len < MAX_OBSERVATIONS {
+                    offset
+                } else {
+                    (self.next_observation + offset) % MAX_OBSERVATIONS
+                };
+                self.observations.get(index).unwrap()
+            })
+            .collect()
+    }
+
+    /// Average spot price between the observations stored at timestamps `start` and `end`.
+    pub fn twap(&self, start: U64, end: U64) -> U128 {
+        require!(start.0 < end.0, "Start must be before end");
+        let find = |timestamp: u64| {
+            self.observations
+                .iter()
+                .find(|observation| observation.timestamp.0 == timestamp)
+                .unwrap_or_else(|| env::panic_str("Observation not found"))
+        };
+        let (start_observation, end_observation) = (find(start.0), find(end.0));
+        let price_delta = end_observation
+            .price_cumulative
+            .0
+            .wrapping_sub(start_observation.price_cumulative.0);
+        (price_delta / u128::from(end.0 - start.0)).into()
+    }
+}
+
+impl Contract {
+    /// Accumulates the spot price since the last update and records an observation.
+    /// Does nothing if no whole second has passed.
+    pub(crate) fn internal_update_price_oracle(&mut self) {
+        let now = now_seconds();
+        if now <= self.price_cumulative_at {
+            return;
+        }
+        let elapsed = now - self.price_cumulative_at;
+        self.price_cumulative = self
+            .price_cumulative
+            .wrapping_add(self.internal_spot_price().wrapping_mul(u128::from(elapsed)));
+        self.price_cumulative_at = now;
+
+        let observation = Observation {
+            timestamp: now.into(),
+            price_cumulative: self.price_cumulative.into(),
+        };
+        if self.observations.len() < MAX_OBSERVATIONS {
+            self.observations.push(&observation);
+        } else {
+            self.observations
+                .replace(self.next_observation, &observation);
+        }
+        self.next_observation = (self.next_observation + 1) % MAX_OBSERVATIONS;
+    }
+}
+
+fn now_seconds() -> u64 {
+    env::block_timestamp() / NANOSECONDS_PER_SECOND
+}
@@ -0,0 +1,177 @@
+use crate::*;
+use near_sdk::json_types::U64;
+use near_sdk::serde::Serialize;
+use near_sdk::{BlockHeight, Timestamp};
+
+const MAX_POLL_OPTIONS: usize = 10;
+
+/// A holder poll. Votes are weighted by the voter's balance at `snapshot_block`,
+/// so coins bought or moved after the poll was created carry no extra weight.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Poll {
+    pub question: String,
+    pub options: Vec<String>,
+    pub snapshot_block: BlockHeight,
+    pub ends_at: Timestamp,
+    pub tallies: Vec<Balance>,
+    pub finalized: bool,
+    pub winner: Option<u32>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PollView {
+    pub id: U64,
+    pub question: String,
+    pub options: Vec<String>,
+    pub snapshot_block: U64,
+    pub ends_at: U64,
+    pub tallies: Vec<U128>,
+    pub finalized: bool,
+    pub winner: Option<u32>,
+}
+
+impl Poll {
+    fn to_view(&self, id: u64) -> PollView {
+        PollView {
+            id: id.into(),
+            question: self.question.clone(),
+            options: self.options.clone(),
+            snapshot_block: self.snapshot_block.into(),
+            ends_at: self.ends_at.into(),
+            tallies: self.tallies.iter().map(|tally| (*tally).into()).collect(),
+            finalized: self.finalized,
+            winner: self.winner,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Opens a poll that holders can vote on until `ends_at` (nanoseconds), weighted by a
+    /// snapshot taken now. The attached deposit pays for the poll's and snapshot's storage.
+    #[payable]
+    pub fn create_poll(&mut self, question: String, options: Vec<String>, ends_at: U64) -> U64 {
+        self.assert_owner();
+        require!(
+            options.len() >= 2 && options.len() <= MAX_POLL_OPTIONS,
+            format!("A poll needs between 2 and {} options", MAX_POLL_OPTIONS)
+        );
+        require!(
+            ends_at.0 > env::block_timestamp(),
+            "Poll must end in the future"
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let snapshot_block = self.internal_take_snapshot(ends_at.0);
+        let id = self.polls.len();
+        self.polls.push(&Poll {
+            question,
+            tallies: vec![0; options.len()],
+            options,
+            snapshot_block,
+            ends_at: ends_at.into(),
+            finalized: false,
+            winner: None,
+        });
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+        log!("Poll {} created, ends at {}", id, ends_at.0);
+        id.into()
+    }
+
+    /// Votes for `option` with the caller's balance at the poll's snapshot block.
+    /// The attached deposit pays for recording the vote.
+    #[payable]
+    pub fn vote(&mut self, poll_id: U64, option: u32) {
+        let account_id = env::predecessor_account_id();
+        let mut poll = self.internal_unwrap_poll(poll_id.0);
+        require!(
+            env::block_timestamp() < poll.ends_at,
+            "Poll has already ended"
+        );
+        require!(
+            env::block_height() > poll.snapshot_block,
+            "Voting opens after the snapshot block"
+        );
+        require!(
+            (option as usize) < poll.options.len(),
+            "Invalid poll option"
+        );
+        let vote_key = (poll_id.0, account_id.clone());
+        require!(
+            !self.poll_votes.contains_key(&vote_key),
+            "Account has already voted"
+        );
+        let weight: Balance = self
+            .ft_balance_of_at(account_id.clone(), poll.snapshot_block.into())
+            .into();
+        require!(weight > 0, "No balance at the snapshot block");
+
+        let initial_storage_usage = env::storage_usage();
+        poll.tallies[option as usize] += weight;
+        self.polls.replace(poll_id.0, &poll);
+        self.poll_votes.insert(&vote_key, &option);
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+        log!(
+            "Account @{} voted {} on poll {} with {}",
+            account_id,
+            option,
+            poll_id.0,
+            weight
+        );
+    }
+
+    /// Records the winning option once the poll has ended. Anyone can call this.
+    /// Ties go to the option listed first.
+    pub fn finalize_poll(&mut self, poll_id: U64) -> u32 {
+        let mut poll = self.internal_unwrap_poll(poll_id.0);
+        require!(!poll.finalized, "Poll is already finalized");
+        require!(
+            env::block_timestamp() >= poll.ends_at,
+            "Poll has not ended yet"
+        );
+
+        let mut winner = 0;
+        for (option, tally) in poll.tallies.iter().enumerate() {
+            if *tally > poll.tallies[winner] {
+                winner = option;
+            }
+        }
+        poll.finalized = true;
+        poll.winner = Some(winner as u32);
+        self.polls.replace(poll_id.0, &poll);
+
+        log!("Poll {} finalized, option {} won", poll_id.0, winner);
+        winner as u32
+    }
+
+    pub fn get_poll(&self, poll_id: U64) -> Option<PollView> {
+        self.polls
+            .get(poll_id.0)
+            .map(|poll| poll.to_view(poll_id.0))
+    }
+
+    pub fn get_polls(&self, from_index: Option<U64>, limit: Option<u64>) -> Vec<PollView> {
+        let from_index = from_index.map(u64::from).unwrap_or(0);
+        let limit = limit.unwrap_or(self.polls.len());
+        (from_index..self.polls.len())
+            .take(limit as usize)
+            .map(|id| self.polls.get(id).unwrap().to_view(id))
+            .collect()
+    }
+
+    /// Option `account_id` voted for on `poll_id`, if any.
+    pub fn poll_vote_of(&self, poll_id: U64, account_id: AccountId) -> Option<u32> {
+        self.poll_votes.get(&(poll_id.0, account_id))
+    }
+}
+
+impl Contract {
+    fn internal_unwrap_poll(&self, poll_id: u64) -> Poll {
+        self.polls
+            .get(poll_id)
+            .unwrap_or_else(|| env::panic_str("Poll not found"))
+    }
+}
@@ -0,0 +1,114 @@
+use crate::*;
+use creator_coin_common::ReferralConfig;
+use near_sdk::serde::Serialize;
+
+const NANOSECONDS_PER_DAY: u64 = 86_400_000_000_000;
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Running totals for a referrer. Earnings are paid into their LZR credit.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ReferralAccount {
+    /// LZR spent on buys the account referred.
+    pub referred_volume: Balance,
+    pub earned: Balance,
+    /// Day number `earned_today` counts towards.
+    pub day: u64,
+    pub earned_today: Balance,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReferralStats {
+    pub referred_volume: U128,
+    pub earned: U128,
+    pub earned_today: U128,
+    /// LZR credit the account can withdraw with `withdraw_lzr_credit`.
+    pub claimable: U128,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Sets the referrer share of the protocol fee. Only the factory can call this,
+    /// see [`creator_coin_common::CREATOR_COIN_REFERRAL_METHOD`].
+    pub fn set_referral_config(&mut self, config: ReferralConfig) {
+        require!(
+            env::predecessor_account_id() == self.factory_id,
+            "Only the factory can configure referrals"
+        );
+        require!(
+            u128::from(config.share_bps) <= BPS_DENOMINATOR,
+            "Share cannot exceed 10000 bps"
+        );
+        self.referral_config = config;
+    }
+
+    pub fn referral_config(&self) -> ReferralConfig {
+        self.referral_config
+    }
+
+    pub fn referral_stats(&self, account_id: AccountId) -> ReferralStats {
+        let referral = self.referrals.get(&account_id).unwrap_or_default();
+        let earned_today = if referral.day == current_day() {
+            referral.earned_today
+        } else {
+            0
+        };
+        ReferralStats {
+            referred_volume: referral.referred_volume.into(),
+            earned: referral.earned.into(),
+            earned_today: earned_today.into(),
+            claimable: self.lzr_credit_of(account_id),
+        }
+    }
+}
+
+impl Contract {
+    /// Records a buy of `amount` LZR referred by `referrer_id` and credits the referrer
+    /// their share of `protocol_fee`, up to the daily cap. Self referrals and referrers
+    /// not registered with the coin earn nothing, and buys that earn nothing are not recorded.
+    pub(crate) fn internal_reward_referrer(
+        &mut self,
+        referrer_id: &AccountId,
+        buyer_id: &AccountId,
+        amount: Balance,
+        protocol_fee: Balance,
+    ) {
+        if referrer_id == buyer_id || !self.token.accounts.contains_key(referrer_id) {
+            return;
+        }
+        let mut referral = self.referrals.get(referrer_id).unwrap_or_default();
+        let today = current_day();
+        if referral.day != today {
+            referral.day = today;
+            referral.earned_today = 0;
+        }
+
+        let share = protocol_fee * Balance::from(self.referral_config.share_bps) / BPS_DENOMINATOR;
+        let reward = share.min(
+            self.referral_config
+                .daily_cap
+                .0
+                .saturating_sub(referral.earned_today),
+        );
+        if reward == 0 {
+            return;
+        }
+        referral.referred_volume += amount;
+        referral.earned += reward;
+        referral.earned_today += reward;
+        self.referrals.insert(referrer_id, &referral);
+
+        let credit = self.lzr_credits.get(referrer_id).unwrap_or(0);
+        self.lzr_credits.insert(referrer_id, &(credit + reward));
+        log!(
+            "Account @{} earned {} LZR for referring @{}",
+            referrer_id,
+            reward,
+            buyer_id
+        );
+    }
+}
+
+fn current_day() -> u64 {
+    env::block_timestamp() / NANOSECONDS_PER_DAY
+}
@@ -0,0 +1,108 @@
+use crate::*;
+use near_contract_standards::fungible_token::events::FtTransfer;
+use near_sdk::serde::Serialize;
+
+/// Highest transfer royalty a creator can set, 10%.
+const MAX_ROYALTY_BPS: u16 = 1_000;
+const BPS_DENOMINATOR: u128 = 10_000;
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TransferRoyaltyView {
+    pub bps: u16,
+    /// Royalties waiting in escrow for the owner to claim.
+    pub unclaimed: U128,
+    pub exemptions: Vec<AccountId>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Sets the royalty in basis points taken from fan to fan transfers. 0 turns it off.
+    pub fn set_transfer_royalty(&mut self, bps: u16) {
+        self.assert_owner();
+        require!(
+            bps <= MAX_ROYALTY_BPS,
+            format!("Royalty cannot exceed {} bps", MAX_ROYALTY_BPS)
+        );
+        self.transfer_royalty_bps = bps;
+    }
+
+    /// Exempts transfers from or to `account_id`, e.g. a router or a DEX, from the royalty.
+    /// The attached deposit pays for storing the exemption.
+    #[payable]
+    pub fn add_royalty_exemption(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        let initial_storage_usage = env::storage_usage();
+        self.royalty_exemptions.insert(&account_id);
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+    }
+
+    pub fn remove_royalty_exemption(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.royalty_exemptions.remove(&account_id);
+    }
+
+    pub fn transfer_royalty(&self) -> TransferRoyaltyView {
+        TransferRoyaltyView {
+            bps: self.transfer_royalty_bps,
+            unclaimed: self.unclaimed_royalties.into(),
+            exemptions: self.royalty_exemptions.to_vec(),
+        }
+    }
+
+    /// Releases the royalties collected so far from escrow to the owner.
+    pub fn claim_royalties(&mut self) -> U128 {
+        self.assert_owner();
+        let amount = self.unclaimed_royalties;
+        require!(amount > 0, "No royalties to claim");
+        self.unclaimed_royalties = 0;
+        let owner_id = self.owner_id.clone();
+        self.internal_escrow_withdraw(&owner_id, amount);
+
+        log!("Account @{} claimed {} royalties", owner_id, amount);
+        amount.into()
+    }
+}
+
+impl Contract {
+    /// Moves the royalty on a transfer of `amount` from `sender_id` into escrow for the owner
+    /// and returns it. Transfers involving the contract, the factory, the owner or an exempt
+    /// account pay nothing.
+    pub(crate) fn internal_take_royalty(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: Balance,
+    ) -> Balance {
+        if self.transfer_royalty_bps == 0
+            || self.is_royalty_exempt(sender_id)
+            || self.is_royalty_exempt(receiver_id)
+        {
+            return 0;
+        }
+        let royalty = amount * Balance::from(self.transfer_royalty_bps) / BPS_DENOMINATOR;
+        if royalty == 0 {
+            return 0;
+        }
+
+        self.internal_escrow_deposit(sender_id, royalty);
+        self.unclaimed_royalties += royalty;
+        let escrow_id = env::current_account_id();
+        let memo = format!("royalty on transfer to @{}", receiver_id);
+        FtTransfer {
+            old_owner_id: sender_id,
+            new_owner_id: &escrow_id,
+            amount: &royalty.into(),
+            memo: Some(&memo),
+        }
+        .emit();
+        royalty
+    }
+
+    fn is_royalty_exempt(&self, account_id: &AccountId) -> bool {
+        *account_id == env::current_account_id()
+            || *account_id == self.factory_id
+            || *account_id == self.owner_id
+            || self.royalty_exemptions.contains(account_id)
+    }
+}
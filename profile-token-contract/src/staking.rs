@@ -0,0 +1,338 @@
+use crate::*;
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{BlockHeight, Timestamp};
+
+/// Scale applied to `reward_per_stake` so small emissions over a large stake still count.
+const REWARD_PRECISION: u128 = 1_000_000_000_000_000_000_000_000;
+const NANOSECONDS_PER_SECOND: u64 = 1_000_000_000;
+/// Longest unbonding period, short enough that coins unstaked when a wind-down starts
+/// unlock well before its redemption deadline.
+const MAX_UNBONDING_PERIOD: u64 = MIN_REDEMPTION_PERIOD / 2;
+
+/// Token the staking reward pool is funded with and paid out in.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum StakingRewardToken {
+    Lzr,
+    CreatorCoin,
+}
+
+/// Global staking state. Rewards are emitted every block pro-rata to the staked amount.
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+pub struct StakingPool {
+    pub reward_token: StakingRewardToken,
+    /// Rewards emitted per block while anything is staked.
+    pub reward_per_block: Balance,
+    /// Nanoseconds unstaked coins stay locked before they can be sold or transferred again.
+    pub unbonding_period: u64,
+    /// Rewards funded but not emitted yet.
+    pub reward_pool: Balance,
+    pub total_staked: Balance,
+    pub reward_per_stake: u128,
+    pub last_reward_block: BlockHeight,
+}
+
+/// Staked coins stay in the holder's balance but cannot be sold or transferred.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct StakeAccount {
+    pub staked: Balance,
+    pub unbonding: Balance,
+    pub unbonding_ends_at: Timestamp,
+    /// Sum of staked amount times seconds staked, up to `last_update`.
+    pub stake_seconds: u128,
+    pub last_update: Timestamp,
+    pub last_reward_per_stake: u128,
+    pub unclaimed_rewards: Balance,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakeView {
+    pub staked: U128,
+    pub unbonding: U128,
+    pub unbonding_ends_at: U64,
+    pub stake_seconds: U128,
+    pub rewards: U128,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakingPoolView {
+    pub reward_token: StakingRewardToken,
+    pub reward_per_block: U128,
+    pub unbonding_period: U64,
+    pub reward_pool: U128,
+    pub total_staked: U128,
+}
+
+impl Default for StakingPool {
+    fn default() -> Self {
+        Self {
+            reward_token: StakingRewardToken::Lzr,
+            reward_per_block: 0,
+            unbonding_period: 0,
+            reward_pool: 0,
+            total_staked: 0,
+            reward_per_stake: 0,
+            last_reward_block: env::block_height(),
+        }
+    }
+}
+
+impl StakingPool {
+    /// Emits the rewards owed since `last_reward_block` to everyone currently staked.
+    fn update(&mut self) {
+        let block_height = env::block_height();
+        if block_height <= self.last_reward_block {
+            return;
+        }
+        if self.total_staked > 0 {
+            let blocks = Balance::from(block_height - self.last_reward_block);
+            let reward = self
+                .reward_per_block
+                .saturating_mul(blocks)
+                .min(self.reward_pool);
+            self.reward_pool -= reward;
+            self.reward_per_stake += (U256::from(reward) * U256::from(REWARD_PRECISION)
+                / U256::from(self.total_staked))
+            .as_u128();
+        }
+        self.last_reward_block = block_height;
+    }
+}
+
+impl StakeAccount {
+    fn stake_seconds_at(&self, now: Timestamp) -> u128 {
+        let seconds = now.saturating_sub(self.last_update) / NANOSECONDS_PER_SECOND;
+        self.stake_seconds
+            .saturating_add(self.staked.saturating_mul(u128::from(seconds)))
+    }
+
+    fn pending_rewards(&self, reward_per_stake: u128) -> Balance {
+        let delta = reward_per_stake - self.last_reward_per_stake;
+        (U256::from(self.staked) * U256::from(delta) / U256::from(REWARD_PRECISION)).as_u128()
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Sets how the staking pool pays out. The reward token can only change while the pool is empty.
+    pub fn configure_staking(
+        &mut self,
+        reward_token: StakingRewardToken,
+        reward_per_block: U128,
+        unbonding_period: U64,
+    ) {
+        self.assert_owner();
+        require!(
+            unbonding_period.0 <= MAX_UNBONDING_PERIOD,
+            format!(
+                "Unbonding period must be at most {} nanoseconds",
+                MAX_UNBONDING_PERIOD
+            )
+        );
+        self.staking.update();
+        require!(
+            reward_token == self.staking.reward_token || self.staking.reward_pool == 0,
+            "Reward pool must be empty to change the reward token"
+        );
+        self.staking.reward_token = reward_token;
+        self.staking.reward_per_block = reward_per_block.into();
+        self.staking.unbonding_period = unbonding_period.into();
+    }
+
+    /// Adds creator coins from the owner's balance to the reward pool.
+    pub fn fund_staking_rewards(&mut self, amount: U128) {
+        self.assert_owner();
+        require!(
+            self.staking.reward_token == StakingRewardToken::CreatorCoin,
+            "Staking rewards are paid in LZR"
+        );
+        let amount: Balance = amount.into();
+        require!(amount > 0, "Amount must be non-zero.");
+        self.staking.update();
+        let owner_id = self.owner_id.clone();
+        self.internal_escrow_deposit(&owner_id, amount);
+        self.staking.reward_pool += amount;
+    }
+
+    /// Locks `amount` of the caller's coins for staking.
+    /// The attached deposit pays for the stake record the first time an account stakes.
+    #[payable]
+    pub fn stake(&mut self, amount: U128) {
+        let account_id = env::predecessor_account_id();
+        let amount: Balance = amount.into();
+        require!(amount > 0, "Amount must be non-zero.");
+        require!(
+            account_id != env::current_account_id(),
+            "Escrowed tokens cannot be staked"
+        );
+        self.internal_assert_unlocked(&account_id, amount);
+
+        let initial_storage_usage = env::storage_usage();
+        let mut stake = self.internal_settle_stake(&account_id);
+        stake.staked += amount;
+        self.staking.total_staked += amount;
+        self.stakes.insert(&account_id, &stake);
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+        log!("Account @{} staked {}", account_id, amount);
+    }
+
+    /// Starts unbonding `amount` of the caller's staked coins.
+    /// Unbonding coins earn nothing and unlock once the unbonding period has passed.
+    pub fn unstake(&mut self, amount: U128) {
+        let account_id = env::predecessor_account_id();
+        let amount: Balance = amount.into();
+        require!(amount > 0, "Amount must be non-zero.");
+
+        let mut stake = self.internal_settle_stake(&account_id);
+        require!(stake.staked >= amount, "Amount exceeds staked balance");
+        stake.staked -= amount;
+        stake.unbonding += amount;
+        stake.unbonding_ends_at = env::block_timestamp() + self.staking.unbonding_period;
+        self.staking.total_staked -= amount;
+        self.stakes.insert(&account_id, &stake);
+
+        log!("Account @{} unstaked {}", account_id, amount);
+    }
+
+    /// Unlocks the caller's unbonded coins once the unbonding period has passed.
+    pub fn withdraw_unstaked(&mut self) -> U128 {
+        let account_id = env::predecessor_account_id();
+        let mut stake = self
+            .stakes
+            .get(&account_id)
+            .unwrap_or_else(|| env::panic_str("No stake"));
+        require!(stake.unbonding > 0, "Nothing is unbonding");
+        require!(
+            env::block_timestamp() >= stake.unbonding_ends_at,
+            "Unbonding period has not passed"
+        );
+
+        let amount = stake.unbonding;
+        stake.unbonding = 0;
+        self.stakes.insert(&account_id, &stake);
+        amount.into()
+    }
+
+    /// Pays the caller the staking rewards they have earned so far.
+    #[payable]
+    pub fn claim_staking_rewards(&mut self) -> PromiseOrValue<U128> {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut stake = self.internal_settle_stake(&account_id);
+        let amount = stake.unclaimed_rewards;
+        require!(amount > 0, "No rewards to claim");
+        stake.unclaimed_rewards = 0;
+        self.stakes.insert(&account_id, &stake);
+
+        match self.staking.reward_token {
+            StakingRewardToken::CreatorCoin => {
+                self.internal_escrow_withdraw(&account_id, amount);
+                PromiseOrValue::Value(amount.into())
+            }
+            StakingRewardToken::Lzr => ext_ft_transfer::ext(self.reserve_token_id.clone())
+                .with_attached_deposit(1)
+                .ft_transfer(account_id.clone(), amount.into())
+                .then(
+                    Self::ext(env::current_account_id())
+                        .on_staking_rewards_claimed(account_id, amount.into()),
+                )
+                .into(),
+        }
+    }
+
+    #[private]
+    pub fn on_staking_rewards_claimed(
+        &mut self,
+        #[callback_result] call_result: Result<(), near_sdk::PromiseError>,
+        account_id: AccountId,
+        amount: U128,
+    ) -> U128 {
+        if call_result.is_err() {
+            let mut stake = self.stakes.get(&account_id).unwrap_or_default();
+            stake.unclaimed_rewards += amount.0;
+            self.stakes.insert(&account_id, &stake);
+            return 0.into();
+        }
+        log!(
+            "Account @{} claimed {} staking rewards",
+            account_id,
+            amount.0
+        );
+        amount
+    }
+
+    pub fn stake_of(&self, account_id: AccountId) -> StakeView {
+        let stake = self.stakes.get(&account_id).unwrap_or_default();
+        let mut staking = self.staking.clone();
+        staking.update();
+        StakeView {
+            staked: stake.staked.into(),
+            unbonding: stake.unbonding.into(),
+            unbonding_ends_at: stake.unbonding_ends_at.into(),
+            stake_seconds: stake.stake_seconds_at(env::block_timestamp()).into(),
+            rewards: (stake.unclaimed_rewards + stake.pending_rewards(staking.reward_per_stake))
+                .into(),
+        }
+    }
+
+    pub fn staking_pool(&self) -> StakingPoolView {
+        let mut staking = self.staking.clone();
+        staking.update();
+        StakingPoolView {
+            reward_token: staking.reward_token,
+            reward_per_block: staking.reward_per_block.into(),
+            unbonding_period: staking.unbonding_period.into(),
+            reward_pool: staking.reward_pool.into(),
+            total_staked: staking.total_staked.into(),
+        }
+    }
+}
+
+impl Contract {
+    /// Adds LZR sent with `ft_transfer_call` to the reward pool.
+    pub(crate) fn internal_fund_staking_rewards_lzr(&mut self, amount: Balance) {
+        require!(
+            self.staking.reward_token == StakingRewardToken::Lzr,
+            "Staking rewards are paid in creator coins"
+        );
+        require!(amount > 0, "Amount must be non-zero.");
+        self.staking.update();
+        self.staking.reward_pool += amount;
+    }
+
+    /// Coins of `account_id` that are staked or unbonding.
+    pub(crate) fn internal_staked_balance(&self, account_id: &AccountId) -> Balance {
+        self.stakes
+            .get(account_id)
+            .map(|stake| stake.staked + stake.unbonding)
+            .unwrap_or(0)
+    }
+
+    /// Drops the stake of an unregistered account, whose staked and unbonding coins were
+    /// burned with the rest of its balance. Rewards it had not claimed go back to the pool.
+    pub(crate) fn internal_remove_stake(&mut self, account_id: &AccountId) {
+        if self.stakes.get(account_id).is_none() {
+            return;
+        }
+        let stake = self.internal_settle_stake(account_id);
+        self.staking.total_staked -= stake.staked;
+        self.staking.reward_pool += stake.unclaimed_rewards;
+        self.stakes.remove(account_id);
+    }
+
+    /// Emits pending pool rewards and brings the stake of `account_id` up to date.
+    fn internal_settle_stake(&mut self, account_id: &AccountId) -> StakeAccount {
+        self.staking.update();
+        let now = env::block_timestamp();
+        let mut stake = self.stakes.get(account_id).unwrap_or_default();
+        stake.unclaimed_rewards += stake.pending_rewards(self.staking.reward_per_stake);
+        stake.last_reward_per_stake = self.staking.reward_per_stake;
+        stake.stake_seconds = stake.stake_seconds_at(now);
+        stake.last_update = now;
+        stake
+    }
+}
@@ -0,0 +1,221 @@
+use crate::*;
+use near_sdk::Gas;
+
+/// Share of every buy paid to the coin's owner, out of [`BUY_FEE_PERCENT`].
+const FOUNDER_REWARD_PERCENT: u128 = 5;
+const SWAP_BUY_GAS: u64 = 100_000_000_000_000;
+const ON_SWAP_SETTLED_GAS: u64 = 10_000_000_000_000;
+
+#[near_bindgen]
+impl Contract {
+    /// Sells `amount` of the caller's coins on this curve and buys `target_coin_id`
+    /// with the LZR proceeds, failing the buy if it would mint less than `min_out`.
+    /// The target must be another coin of the same factory.
+    /// LZR the target does not use is credited to the caller, see `withdraw_lzr_credit`.
+    #[payable]
+    pub fn swap(
+        &mut self,
+        amount: U128,
+        target_coin_id: AccountId,
+        min_out: Option<U128>,
+    ) -> Promise {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        require!(
+            target_coin_id != env::current_account_id()
+                && target_coin_id
+                    .as_str()
+                    .strip_suffix(self.factory_id.as_str())
+                    .is_some_and(|name| name.ends_with('.')),
+            "Target must be another coin of the same factory"
+        );
+
+        let proceeds = self.internal_sell(account_id.clone(), amount.into());
+        let msg = BuyMessage {
+            receiver_id: Some(account_id.clone()),
+            min_out,
+            referrer_id: None,
+        };
+        ext_ft_transfer::ext(self.reserve_token_id.clone())
+            .with_attached_deposit(1)
+            .with_static_gas(Gas(SWAP_BUY_GAS))
+            .ft_transfer_call(target_coin_id.clone(), proceeds.into(), None, msg.to_msg())
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(Gas(ON_SWAP_SETTLED_GAS))
+                    .on_swap_settled(account_id, target_coin_id, proceeds.into()),
+            )
+    }
+
+    /// Sells `amount` of the caller's coins back to the curve and sends them the LZR
+    /// proceeds, failing if the proceeds are below `min_out`.
+    #[payable]
+    pub fn sell(&mut self, amount: U128, min_out: Option<U128>) -> Promise {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let proceeds = self.internal_sell(account_id.clone(), amount.into());
+        require!(
+            proceeds >= min_out.map(Balance::from).unwrap_or(0),
+            "LZR returned is below min_out"
+        );
+
+        ext_ft_transfer::ext(self.reserve_token_id.clone())
+            .with_attached_deposit(1)
+            .ft_transfer(account_id.clone(), proceeds.into())
+            .then(
+                Self::ext(env::current_account_id()).on_burn_transfer_callback(
+                    amount,
+                    proceeds.into(),
+                    account_id,
+                    env::attached_deposit().into(),
+                ),
+            )
+    }
+
+    /// Callback after swap proceeds were sent to the target coin.
+    /// Credits whatever the target did not use to `account_id` and returns the LZR used.
+    #[private]
+    pub fn on_swap_settled(
+        &mut self,
+        #[callback_result] used: Result<U128, near_sdk::PromiseError>,
+        account_id: AccountId,
+        target_coin_id: AccountId,
+        proceeds: U128,
+    ) -> U128 {
+        let used = used.map_or(0, |used| used.0.min(proceeds.0));
+        let unused = proceeds.0 - used;
+        if unused > 0 {
+            let credit = self.lzr_credits.get(&account_id).unwrap_or(0);
+            self.lzr_credits.insert(&account_id, &(credit + unused));
+        }
+        log!(
+            "Account @{} swapped {} LZR into @{}, {} LZR credited",
+            account_id,
+            used,
+            target_coin_id,
+            unused
+        );
+        used.into()
+    }
+
+    /// Sends the caller the LZR credited to them by swaps.
+    #[payable]
+    pub fn withdraw_lzr_credit(&mut self) -> Promise {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let amount = self.lzr_credits.remove(&account_id).unwrap_or(0);
+        require!(amount > 0, "No LZR credit to withdraw");
+
+        ext_ft_transfer::ext(self.reserve_token_id.clone())
+            .with_attached_deposit(1)
+            .ft_transfer(account_id.clone(), amount.into())
+            .then(
+                Self::ext(env::current_account_id())
+                    .on_lzr_credit_withdrawn(account_id, amount.into()),
+            )
+    }
+
+    #[private]
+    pub fn on_lzr_credit_withdrawn(
+        &mut self,
+        #[callback_result] call_result: Result<(), near_sdk::PromiseError>,
+        account_id: AccountId,
+        amount: U128,
+    ) -> U128 {
+        if call_result.is_err() {
+            let credit = self.lzr_credits.get(&account_id).unwrap_or(0);
+            self.lzr_credits.insert(&account_id, &(credit + amount.0));
+            return 0.into();
+        }
+        log!("Account @{} withdrew {} LZR credit", account_id, amount.0);
+        amount
+    }
+
+    pub fn lzr_credit_of(&self, account_id: AccountId) -> U128 {
+        self.lzr_credits.get(&account_id).unwrap_or(0).into()
+    }
+}
+
+impl Contract {
+    /// Mints coins to `receiver_id` for `amount` LZR received with `ft_transfer_call`.
+    /// The buy fee stays out of the reserve. Part of it goes to the owner and, if the buy
+    /// was referred, part of the protocol's share goes to `referrer_id`.
+    /// `receiver_id` must already be registered, so buyers pay for their own storage.
+    pub(crate) fn internal_buy(
+        &mut self,
+        receiver_id: AccountId,
+        amount: Balance,
+        min_out: Balance,
+        referrer_id: Option<AccountId>,
+    ) -> Balance {
+        self.assert_not_wound_down();
+        require!(
+            self.token.accounts.contains_key(&receiver_id),
+            "Receiver is not registered"
+        );
+        let deposit_amount = amount - (amount * BUY_FEE_PERCENT) / 100;
+        require!(deposit_amount > 0, "Must send loozr to buy tokens");
+        let minted: Balance = self
+            .continous_mint(deposit_amount, receiver_id.clone())
+            .into();
+        require!(minted >= min_out, "Coins minted are below min_out");
+
+        let founder_reward = (amount * FOUNDER_REWARD_PERCENT) / 100;
+        if let Some(referrer_id) = referrer_id {
+            let protocol_fee = (amount - deposit_amount).saturating_sub(founder_reward);
+            self.internal_reward_referrer(&referrer_id, &receiver_id, amount, protocol_fee);
+        }
+        if founder_reward > 0 {
+            ext_ft_transfer::ext(self.reserve_token_id.clone())
+                .with_attached_deposit(1)
+                .ft_transfer(self.owner_id.clone(), founder_reward.into());
+        }
+
+        log!(
+            "Account @{} bought {} for {} LZR",
+            receiver_id,
+            minted,
+            amount
+        );
+        minted
+    }
+
+    /// Burns `sell_amount` of the unlocked coins of `account_id` on the curve and takes the
+    /// LZR they are worth out of the reserve. Returns that amount, which the caller pays out.
+    pub(crate) fn internal_sell(&mut self, account_id: AccountId, sell_amount: Balance) -> Balance {
+        self.assert_not_wound_down();
+        require!(sell_amount > 0, "Amount must be non-zero.");
+        require!(
+            account_id != env::current_account_id(),
+            "Escrowed tokens cannot be sold"
+        );
+
+        let balance = self
+            .internal_unwrap_balance_of(account_id.clone())
+            .saturating_sub(self.internal_locked_balance(&account_id));
+        require!(
+            balance >= sell_amount,
+            "Amount exceeds creator coin locked in"
+        );
+        require!(
+            self.lzr_locked > 0
+                && self.token.total_supply > 0
+                && sell_amount <= self.token.total_supply,
+            "Amount exceeds creator coin in supply"
+        );
+
+        let amount_in_near = Decimal::from_i128_with_scale(sell_amount as i128, TOKEN_DECIMAL);
+        let lzr_locked_in_near =
+            Decimal::from_i128_with_scale(self.lzr_locked as i128, TOKEN_DECIMAL);
+        let current_supply_in_near =
+            Decimal::from_i128_with_scale(self.token.total_supply as i128, TOKEN_DECIMAL);
+
+        self.continous_sale(
+            current_supply_in_near,
+            lzr_locked_in_near,
+            amount_in_near,
+            sell_amount,
+            account_id,
+        )
+    }
+}
@@ -0,0 +1,67 @@
+use crate::*;
+use creator_coin_common::CREATOR_COIN_MIGRATE_METHOD;
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::Gas;
+
+const MIGRATE_CALL_GAS: u64 = 20_000_000_000_000;
+
+#[near_bindgen]
+impl Contract {
+    /// Lets the factory replace this coin's code with the wasm whose sha256 is `code_hash`.
+    /// The approval is used up by the next update.
+    pub fn approve_code_update(&mut self, code_hash: Base58CryptoHash) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only the owner can approve code updates"
+        );
+        self.approved_code_hash = Some(code_hash.into());
+        log!(
+            "Account @{} approved code update {}",
+            self.owner_id,
+            String::from(&code_hash)
+        );
+    }
+
+    pub fn approved_code_update(&self) -> Option<Base58CryptoHash> {
+        self.approved_code_hash.map(Base58CryptoHash::from)
+    }
+
+    pub fn factory_id(&self) -> AccountId {
+        self.factory_id.clone()
+    }
+
+    /// Deploys the wasm passed as raw input and migrates the state to it, see
+    /// [`creator_coin_common::CREATOR_COIN_UPDATE_METHOD`].
+    /// Coin accounts hold no full access keys, so this is the only way to change their code.
+    pub fn update_code(&mut self) -> Promise {
+        require!(
+            env::predecessor_account_id() == self.factory_id,
+            "Only the factory can update the code"
+        );
+        let code = env::input().unwrap_or_else(|| env::panic_str("Missing code"));
+        let approved_code_hash = self
+            .approved_code_hash
+            .take()
+            .unwrap_or_else(|| env::panic_str("Code update has not been approved"));
+        require!(
+            env::sha256_array(&code) == approved_code_hash,
+            "Code does not match the approved update"
+        );
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                CREATOR_COIN_MIGRATE_METHOD.into(),
+                vec![],
+                0,
+                Gas(MIGRATE_CALL_GAS),
+            )
+    }
+
+    /// Runs in the new code right after `update_code` deployed it.
+    /// Versions that change the state layout convert the old state here.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        env::state_read().unwrap_or_else(|| env::panic_str("Coin is not initialized"))
+    }
+}
@@ -0,0 +1,173 @@
+use crate::*;
+use near_sdk::json_types::U64;
+use near_sdk::serde::Serialize;
+use near_sdk::Timestamp;
+
+/// Coins granted to a beneficiary that unlock linearly after a cliff.
+/// Locked coins sit in escrow on the contract's own account until claimed.
+#[derive(BorshDeserialize, BorshSerialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingSchedule {
+    pub total: U128,
+    pub claimed: U128,
+    /// Nanosecond timestamp the schedule starts counting from.
+    pub start: U64,
+    /// Nanoseconds after `start` before anything unlocks.
+    pub cliff: U64,
+    /// Nanoseconds after `start` until everything is unlocked.
+    pub duration: U64,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingBalance {
+    pub vested: U128,
+    pub unvested: U128,
+    pub claimed: U128,
+}
+
+impl VestingSchedule {
+    /// Amount unlocked at `now`, including whatever was already claimed.
+    pub fn vested_amount(&self, now: Timestamp) -> Balance {
+        let total: Balance = self.total.into();
+        let elapsed = now.saturating_sub(self.start.0);
+        if elapsed < self.cliff.0 {
+            return 0;
+        }
+        if elapsed >= self.duration.0 {
+            return total;
+        }
+
+        // total * elapsed / duration, split so the product cannot overflow
+        let duration = Balance::from(self.duration.0);
+        let elapsed = Balance::from(elapsed);
+        (total / duration) * elapsed + (total % duration) * elapsed / duration
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Moves `total` coins from the owner into escrow and vests them to `beneficiary_id`.
+    /// The attached deposit pays for the schedule's storage.
+    #[payable]
+    pub fn create_vesting_schedule(
+        &mut self,
+        beneficiary_id: AccountId,
+        total: U128,
+        start: U64,
+        cliff: U64,
+        duration: U64,
+    ) {
+        self.assert_owner();
+        let amount: Balance = total.into();
+        require!(amount > 0, "Amount must be non-zero.");
+        require!(duration.0 > 0, "Duration must be non-zero");
+        require!(cliff.0 <= duration.0, "Cliff cannot exceed duration");
+        require!(
+            beneficiary_id != env::current_account_id(),
+            "Cannot vest to the contract"
+        );
+        require!(
+            !self.vesting_schedules.contains_key(&beneficiary_id),
+            "Beneficiary already has a vesting schedule"
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let owner_id = self.owner_id.clone();
+        self.internal_escrow_deposit(&owner_id, amount);
+        self.vesting_schedules.insert(
+            &beneficiary_id,
+            &VestingSchedule {
+                total,
+                claimed: 0.into(),
+                start,
+                cliff,
+                duration,
+            },
+        );
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+        log!(
+            "Vesting {} to @{} starting at {}",
+            amount,
+            beneficiary_id,
+            start.0
+        );
+    }
+
+    /// Releases the unlocked, unclaimed portion of the caller's schedule.
+    pub fn claim_vested(&mut self) -> U128 {
+        let account_id = env::predecessor_account_id();
+        let mut schedule = self
+            .vesting_schedules
+            .get(&account_id)
+            .unwrap_or_else(|| env::panic_str("No vesting schedule"));
+
+        let claimed: Balance = schedule.claimed.into();
+        let claimable = schedule.vested_amount(env::block_timestamp()) - claimed;
+        require!(claimable > 0, "Nothing to claim");
+
+        schedule.claimed = (claimed + claimable).into();
+        self.vesting_schedules.insert(&account_id, &schedule);
+        self.internal_escrow_withdraw(&account_id, claimable);
+
+        log!("Account @{} claimed {} vested", account_id, claimable);
+        claimable.into()
+    }
+
+    pub fn vesting_schedule_of(&self, account_id: AccountId) -> Option<VestingSchedule> {
+        self.vesting_schedules.get(&account_id)
+    }
+
+    pub fn vesting_balance_of(&self, account_id: AccountId) -> VestingBalance {
+        match self.vesting_schedules.get(&account_id) {
+            Some(schedule) => {
+                let vested = schedule.vested_amount(env::block_timestamp());
+                VestingBalance {
+                    vested: vested.into(),
+                    unvested: (schedule.total.0 - vested).into(),
+                    claimed: schedule.claimed,
+                }
+            }
+            None => VestingBalance {
+                vested: 0.into(),
+                unvested: 0.into(),
+                claimed: 0.into(),
+            },
+        }
+    }
+}
+
+impl Contract {
+    /// The coin account itself counts as the owner, so a function-call key the factory
+    /// added for [`creator_coin_common::CREATOR_ADMIN_METHODS`] can call them.
+    pub(crate) fn assert_owner(&self) {
+        let predecessor_id = env::predecessor_account_id();
+        require!(
+            predecessor_id == self.owner_id || predecessor_id == env::current_account_id(),
+            "Only the owner can call this method"
+        );
+    }
+
+    /// Moves coins from `account_id` onto the contract's own account.
+    pub(crate) fn internal_escrow_deposit(&mut self, account_id: &AccountId, amount: Balance) {
+        self.internal_assert_unlocked(account_id, amount);
+        let balance = self.internal_unwrap_balance_of(account_id.clone());
+        let escrow_id = env::current_account_id();
+        let escrow_balance = self.internal_unwrap_balance_of(escrow_id.clone());
+
+        self.internal_update_account(account_id, balance - amount);
+        self.internal_update_account(&escrow_id, escrow_balance + amount);
+    }
+
+    /// Releases coins held by the contract's own account to `account_id`.
+    pub(crate) fn internal_escrow_withdraw(&mut self, account_id: &AccountId, amount: Balance) {
+        let escrow_id = env::current_account_id();
+        let escrow_balance = self.internal_unwrap_balance_of(escrow_id.clone());
+        require!(escrow_balance >= amount, "NOT ENOUGH BALANCE");
+        let balance = self.internal_unwrap_balance_of(account_id.clone());
+
+        self.internal_update_account(&escrow_id, escrow_balance - amount);
+        self.internal_update_account(account_id, balance + amount);
+    }
+}
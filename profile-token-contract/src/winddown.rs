@@ -0,0 +1,191 @@
+//! Wind-down of a coin whose creator left the platform or that was delisted. Buys, sells
+//! and transfers stop, and every holder can redeem their coins for their exact share of
+//! the reserve, `balance / total_supply * lzr_locked`, no matter who redeems first.
+//! Whatever is left in the reserve after the redemption deadline goes to the treasury.
+
+use crate::*;
+use near_sdk::json_types::U64;
+use near_sdk::serde::Serialize;
+
+/// Shortest time holders get to redeem, 30 days.
+pub(crate) const MIN_REDEMPTION_PERIOD: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WindDown {
+    pub started_at: U64,
+    /// Redemptions close at this timestamp and the rest of the reserve can be swept.
+    pub deadline: U64,
+    pub treasury_id: AccountId,
+    pub swept: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Starts winding the coin down. Holders have `redemption_period` nanoseconds to redeem.
+    /// Callable by the owner or the factory. Only the factory can pick `treasury_id`; when
+    /// omitted the remaining reserve goes to the factory, whose admins forward it with
+    /// `withdraw_lzr`.
+    /// The factory calls it as [`creator_coin_common::CREATOR_COIN_WIND_DOWN_METHOD`].
+    pub fn wind_down(&mut self, redemption_period: U64, treasury_id: Option<AccountId>) {
+        let predecessor_id = env::predecessor_account_id();
+        require!(
+            predecessor_id == self.owner_id || predecessor_id == self.factory_id,
+            "Only the owner or the factory can wind down the coin"
+        );
+        require!(
+            treasury_id.is_none() || predecessor_id == self.factory_id,
+            "Only the factory can choose the treasury"
+        );
+        require!(self.wind_down.is_none(), "Coin is already winding down");
+        require!(
+            redemption_period.0 >= MIN_REDEMPTION_PERIOD,
+            format!(
+                "Redemption period must be at least {} nanoseconds",
+                MIN_REDEMPTION_PERIOD
+            )
+        );
+
+        let now = env::block_timestamp();
+        self.wind_down = Some(WindDown {
+            started_at: now.into(),
+            deadline: (now + redemption_period.0).into(),
+            treasury_id: treasury_id.unwrap_or_else(|| self.factory_id.clone()),
+            swept: false,
+        });
+        log!(
+            "Coin winding down by @{}, redemptions close at {}",
+            predecessor_id,
+            now + redemption_period.0
+        );
+    }
+
+    pub fn wind_down_state(&self) -> Option<WindDown> {
+        self.wind_down.clone()
+    }
+
+    /// LZR `account_id` would get for redeeming all their unlocked coins now.
+    pub fn redemption_value_of(&self, account_id: AccountId) -> U128 {
+        let balance = self
+            .token
+            .accounts
+            .get(&account_id)
+            .unwrap_or(0)
+            .saturating_sub(self.internal_locked_balance(&account_id));
+        self.internal_redemption_value(balance).into()
+    }
+
+    /// Burns all of the caller's unlocked coins and pays them their share of the reserve.
+    #[payable]
+    pub fn redeem(&mut self) -> Promise {
+        assert_one_yocto();
+        let wind_down = self
+            .wind_down
+            .as_ref()
+            .unwrap_or_else(|| env::panic_str("Coin is not winding down"));
+        require!(
+            env::block_timestamp() < wind_down.deadline.0,
+            "Redemption period is over"
+        );
+        let account_id = env::predecessor_account_id();
+        require!(
+            account_id != env::current_account_id(),
+            "Escrowed tokens cannot be redeemed"
+        );
+
+        let amount = self
+            .internal_unwrap_balance_of(account_id.clone())
+            .saturating_sub(self.internal_locked_balance(&account_id));
+        require!(amount > 0, "No unlocked coins to redeem");
+        let payout = self.internal_redemption_value(amount);
+        self.internal_burn(amount, account_id.clone());
+        self.lzr_locked -= payout;
+
+        ext_ft_transfer::ext(self.reserve_token_id.clone())
+            .with_attached_deposit(1)
+            .ft_transfer(account_id.clone(), payout.into())
+            .then(Self::ext(env::current_account_id()).on_redeem_transfer(
+                account_id,
+                amount.into(),
+                payout.into(),
+            ))
+    }
+
+    /// Restores the redeemed coins and the reserve if the payout failed.
+    #[private]
+    pub fn on_redeem_transfer(
+        &mut self,
+        #[callback_result] call_result: Result<(), near_sdk::PromiseError>,
+        account_id: AccountId,
+        amount: U128,
+        payout: U128,
+    ) -> U128 {
+        if call_result.is_err() {
+            self.internal_mint(amount.into(), account_id);
+            self.lzr_locked += payout.0;
+            return 0.into();
+        }
+        log!(
+            "Account @{} redeemed {} for {} LZR",
+            account_id,
+            amount.0,
+            payout.0
+        );
+        payout
+    }
+
+    /// Sends what is left of the reserve to the treasury once the redemption deadline passed.
+    /// Callable by anyone.
+    pub fn sweep_wind_down_dust(&mut self) -> Promise {
+        let wind_down = self
+            .wind_down
+            .as_mut()
+            .unwrap_or_else(|| env::panic_str("Coin is not winding down"));
+        require!(
+            env::block_timestamp() >= wind_down.deadline.0,
+            "Redemption period has not ended"
+        );
+        require!(!wind_down.swept, "Reserve was already swept");
+        wind_down.swept = true;
+        let treasury_id = wind_down.treasury_id.clone();
+        let amount = self.lzr_locked;
+        self.lzr_locked = 0;
+
+        ext_ft_transfer::ext(self.reserve_token_id.clone())
+            .with_attached_deposit(1)
+            .ft_transfer(treasury_id, amount.into())
+            .then(Self::ext(env::current_account_id()).on_wind_down_swept(amount.into()))
+    }
+
+    #[private]
+    pub fn on_wind_down_swept(
+        &mut self,
+        #[callback_result] call_result: Result<(), near_sdk::PromiseError>,
+        amount: U128,
+    ) -> U128 {
+        if call_result.is_err() {
+            self.lzr_locked += amount.0;
+            if let Some(wind_down) = self.wind_down.as_mut() {
+                wind_down.swept = false;
+            }
+            return 0.into();
+        }
+        log!("Swept {} LZR of remaining reserve", amount.0);
+        amount
+    }
+}
+
+impl Contract {
+    /// Panics once the coin is winding down, where only redemptions are allowed.
+    pub(crate) fn assert_not_wound_down(&self) {
+        require!(self.wind_down.is_none(), "Coin is winding down");
+    }
+
+    fn internal_redemption_value(&self, amount: Balance) -> Balance {
+        if self.token.total_supply == 0 {
+            return 0;
+        }
+        (U256::from(amount) * U256::from(self.lzr_locked) / U256::from(self.token.total_supply))
+            .as_u128()
+    }
+}